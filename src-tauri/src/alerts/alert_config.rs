@@ -0,0 +1,266 @@
+﻿use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use serde::Deserialize;
+use crate::alerts::alert_rule::{AlertCondition, AlertRule};
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AlertConfig {
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    #[serde(default)]
+    pub player_whitelist: Vec<String>,
+    /// Players whose standing column is at or above this value are never reported.
+    pub ignore_standing_at_or_above: Option<f64>,
+    #[serde(default)]
+    pub characters: HashMap<String, CharacterAlertOverride>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CharacterAlertOverride {
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+    #[serde(default)]
+    pub player_whitelist: Vec<String>,
+    pub ignore_standing_at_or_above: Option<f64>,
+}
+
+/// Rules, whitelist and standing threshold once a character override has been applied.
+#[derive(Debug, Clone)]
+pub struct EffectiveAlertSettings {
+    pub rules: Vec<AlertRule>,
+    pub player_whitelist: HashSet<String>,
+    pub ignore_standing_at_or_above: Option<f64>,
+}
+
+impl AlertConfig {
+
+    pub fn parse(content: &str) -> Result<AlertConfig, String> {
+        let config: AlertConfig = toml::from_str(content).map_err(|error| error.to_string())?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        AlertConfig::validate_rules(&self.rules, "rules")?;
+
+        for (character, character_override) in &self.characters {
+            AlertConfig::validate_rules(&character_override.rules, &format!("characters.{}.rules", character))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_rules(rules: &[AlertRule], section: &str) -> Result<(), String> {
+        let mut ids = HashSet::new();
+
+        for rule in rules {
+            if rule.id.trim().is_empty() {
+                return Err(format!("{}: rule id can not be empty", section));
+            }
+            if !ids.insert(rule.id.as_str()) {
+                return Err(format!("{}: duplicated rule id '{}'", section, rule.id));
+            }
+            if let AlertCondition::NonWhitelistedPlayerInRange { max_distance_in_meters } = rule.condition {
+                if max_distance_in_meters <= 0 {
+                    return Err(format!(
+                        "{}: rule '{}' needs a positive max_distance_in_meters",
+                        section, rule.id
+                    ));
+                }
+            }
+            if let AlertCondition::NonWhitelistedPlayerClosing { max_time_to_contact_in_seconds } = rule.condition {
+                if max_time_to_contact_in_seconds <= 0.0 {
+                    return Err(format!(
+                        "{}: rule '{}' needs a positive max_time_to_contact_in_seconds",
+                        section, rule.id
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn settings_for_character(&self, character_name: Option<&str>) -> EffectiveAlertSettings {
+        let mut rules = if self.rules.is_empty() {
+            AlertRule::default_rules()
+        } else {
+            self.rules.clone()
+        };
+        let mut player_whitelist = self.player_whitelist.iter().cloned().collect::<HashSet<_>>();
+        let mut ignore_standing_at_or_above = self.ignore_standing_at_or_above;
+
+        if let Some(character_override) = character_name.and_then(|name| self.characters.get(name)) {
+            rules.retain(|rule| {
+                !character_override.disabled_rules.contains(&rule.id)
+                    && !character_override.rules.iter().any(|override_rule| override_rule.id == rule.id)
+            });
+            rules.extend(character_override.rules.iter().cloned());
+            player_whitelist.extend(character_override.player_whitelist.iter().cloned());
+            ignore_standing_at_or_above = character_override
+                .ignore_standing_at_or_above
+                .or(ignore_standing_at_or_above);
+        }
+
+        EffectiveAlertSettings {
+            rules,
+            player_whitelist,
+            ignore_standing_at_or_above,
+        }
+    }
+}
+
+/// Polls the modification time of the config file so edits are picked up between frames.
+#[derive(Debug)]
+pub struct AlertConfigWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+}
+
+impl AlertConfigWatcher {
+
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        AlertConfigWatcher {
+            path: path.as_ref().to_path_buf(),
+            last_modified: None,
+        }
+    }
+
+    /// Returns `None` while the file is unchanged (or missing), otherwise the result of
+    /// parsing its new content.
+    pub fn poll(&mut self) -> Option<Result<AlertConfig, String>> {
+        let modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok()?;
+
+        if self.last_modified == Some(modified) {
+            return None;
+        }
+        self.last_modified = Some(modified);
+
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) => return Some(Err(format!("{}: {}", self.path.display(), error))),
+        };
+
+        Some(
+            AlertConfig::parse(&content)
+                .map_err(|error| format!("{}: {}", self.path.display(), error)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::alerts::alert_config::AlertConfig;
+    use crate::alerts::alert_rule::{AlertCondition, AlertRule};
+
+    const CONFIG: &str = r#"
+player_whitelist = ["Friend"]
+ignore_standing_at_or_above = 5.0
+
+[[rules]]
+id = "hostile_player_on_grid"
+cooldown_in_seconds = 20
+condition = { type = "non_whitelisted_player_in_range", max_distance_in_meters = 50000 }
+
+[[rules]]
+id = "jammed"
+cooldown_in_seconds = 10
+condition = { type = "jamming_me" }
+
+[characters."Main Pilot"]
+disabled_rules = ["jammed"]
+player_whitelist = ["Corp Mate"]
+ignore_standing_at_or_above = 0.5
+
+[[characters."Main Pilot".rules]]
+id = "hostile_player_on_grid"
+cooldown_in_seconds = 5
+condition = { type = "non_whitelisted_player_in_range", max_distance_in_meters = 1000000 }
+"#;
+
+    fn rule_ids(rules: &[AlertRule]) -> Vec<&str> {
+        rules.iter().map(|rule| rule.id.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_rules_whitelist_and_standing() {
+        let config = AlertConfig::parse(CONFIG).unwrap();
+
+        assert_eq!(rule_ids(&config.rules), vec!["hostile_player_on_grid", "jammed"]);
+        assert_eq!(
+            config.rules[0].condition,
+            AlertCondition::NonWhitelistedPlayerInRange { max_distance_in_meters: 50_000 }
+        );
+        assert_eq!(config.rules[0].cooldown, Duration::from_secs(20));
+        assert_eq!(config.player_whitelist, vec!["Friend".to_string()]);
+        assert_eq!(config.ignore_standing_at_or_above, Some(5.0));
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(AlertConfig::parse("player_whitelist = []\nunknown_setting = true").is_err());
+        assert!(AlertConfig::parse("[characters.\"Main Pilot\"]\nwhitelist = [\"Friend\"]").is_err());
+    }
+
+    #[test]
+    fn rejects_duplicated_rule_ids() {
+        let content = r#"
+[[rules]]
+id = "jammed"
+cooldown_in_seconds = 10
+condition = { type = "jamming_me" }
+
+[[rules]]
+id = "jammed"
+cooldown_in_seconds = 20
+condition = { type = "jamming_me" }
+"#;
+
+        assert!(AlertConfig::parse(content).unwrap_err().contains("duplicated rule id"));
+    }
+
+    #[test]
+    fn empty_config_uses_default_rules() {
+        let settings = AlertConfig::parse("").unwrap().settings_for_character(None);
+
+        assert_eq!(rule_ids(&settings.rules), rule_ids(&AlertRule::default_rules()));
+        assert!(settings.player_whitelist.is_empty());
+        assert_eq!(settings.ignore_standing_at_or_above, None);
+    }
+
+    #[test]
+    fn character_override_is_merged_over_the_global_settings() {
+        let config = AlertConfig::parse(CONFIG).unwrap();
+
+        let settings = config.settings_for_character(Some("Main Pilot"));
+
+        assert_eq!(rule_ids(&settings.rules), vec!["hostile_player_on_grid"]);
+        assert_eq!(
+            settings.rules[0].condition,
+            AlertCondition::NonWhitelistedPlayerInRange { max_distance_in_meters: 1_000_000 }
+        );
+        assert_eq!(settings.rules[0].cooldown, Duration::from_secs(5));
+        assert!(settings.player_whitelist.contains("Friend"));
+        assert!(settings.player_whitelist.contains("Corp Mate"));
+        assert_eq!(settings.ignore_standing_at_or_above, Some(0.5));
+    }
+
+    #[test]
+    fn other_characters_keep_the_global_settings() {
+        let config = AlertConfig::parse(CONFIG).unwrap();
+
+        let settings = config.settings_for_character(Some("Alt Pilot"));
+
+        assert_eq!(rule_ids(&settings.rules), vec!["hostile_player_on_grid", "jammed"]);
+        assert_eq!(settings.player_whitelist.len(), 1);
+        assert_eq!(settings.ignore_standing_at_or_above, Some(5.0));
+    }
+}
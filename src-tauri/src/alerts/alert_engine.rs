@@ -0,0 +1,431 @@
+﻿use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use crate::alerts::alert_config::EffectiveAlertSettings;
+use crate::alerts::alert_event::{AlertEvent, AlertKind};
+use crate::alerts::alert_rule::{AlertCondition, AlertRule};
+use crate::eve::ui::models::general_window::GeneralWindow;
+use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+use crate::eve::ui::parser_utils::ParserUtils;
+
+/// Last trigger time per (rule id, subject).
+pub type AlertCooldowns = HashMap<(String, Option<String>), Instant>;
+
+#[derive(Debug)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    player_whitelist: HashSet<String>,
+    ignore_standing_at_or_above: Option<f64>,
+    last_triggered: AlertCooldowns,
+}
+
+impl AlertEngine {
+
+    pub fn new(rules: Vec<AlertRule>, player_whitelist: HashSet<String>) -> Self {
+        AlertEngine {
+            rules,
+            player_whitelist,
+            ignore_standing_at_or_above: None,
+            last_triggered: HashMap::new(),
+        }
+    }
+
+    /// Swaps rules and whitelist after a config reload, keeping cooldowns of rules that still exist.
+    pub fn apply_settings(&mut self, settings: EffectiveAlertSettings) {
+        self.last_triggered
+            .retain(|(rule_id, _), _| settings.rules.iter().any(|rule| &rule.id == rule_id));
+
+        self.rules = settings.rules;
+        self.player_whitelist = settings.player_whitelist;
+        self.ignore_standing_at_or_above = settings.ignore_standing_at_or_above;
+    }
+
+    /// Hands the cooldowns over so they can follow the character to its next client.
+    pub fn take_cooldowns(&mut self) -> AlertCooldowns {
+        std::mem::take(&mut self.last_triggered)
+    }
+
+    pub fn restore_cooldowns(&mut self, cooldowns: AlertCooldowns) {
+        self.last_triggered = cooldowns;
+    }
+
+    /// Evaluates every rule against one parsed frame. `now` is passed in so cooldowns
+    /// can be driven without a running clock.
+    pub fn evaluate(&mut self, general_window: &GeneralWindow, now: Instant) -> Vec<AlertEvent> {
+        let candidates = self
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                AlertEngine::matches(rule, general_window, |entry| self.is_ignored_player(entry))
+                    .into_iter()
+                    .map(move |event| (rule, event))
+            })
+            .collect::<Vec<_>>();
+
+        candidates
+            .into_iter()
+            .filter_map(|(rule, event)| {
+                let cooldown_key = (rule.id.clone(), event.subject.clone());
+
+                if let Some(last_time) = self.last_triggered.get(&cooldown_key) {
+                    if now.duration_since(*last_time) < rule.cooldown {
+                        return None;
+                    }
+                }
+
+                self.last_triggered.insert(cooldown_key, now);
+                Some(event)
+            })
+            .collect()
+    }
+
+    /// Names of the players on overview that are neither whitelisted nor in good standing.
+    pub fn hostile_players(&self, general_window: &GeneralWindow) -> Vec<String> {
+        general_window
+            .overview_windows
+            .iter()
+            .flat_map(|overview| overview.entries.iter())
+            .filter(|entry| entry.is_player)
+            .filter(|entry| !self.is_ignored_player(entry))
+            .filter_map(|entry| entry.object_name.clone())
+            .collect()
+    }
+
+    fn is_ignored_player(&self, entry: &OverviewWindowEntry) -> bool {
+        let whitelisted = entry
+            .object_name
+            .as_ref()
+            .is_some_and(|name| self.player_whitelist.contains(name));
+
+        let good_standing = match self.ignore_standing_at_or_above {
+            Some(threshold) => entry
+                .cells_texts
+                .get(t!("standing").as_ref())
+                .and_then(|standing| ParserUtils::parse_decimal_number(standing))
+                .is_some_and(|standing| standing >= threshold),
+            None => false,
+        };
+
+        whitelisted || good_standing
+    }
+
+    fn matches<F>(
+        rule: &AlertRule,
+        general_window: &GeneralWindow,
+        is_ignored_player: F,
+    ) -> Vec<AlertEvent>
+    where
+        F: Fn(&OverviewWindowEntry) -> bool,
+    {
+        let overview_entries = general_window
+            .overview_windows
+            .iter()
+            .flat_map(|overview| overview.entries.iter());
+
+        match &rule.condition {
+            AlertCondition::NonWhitelistedPlayerInRange { max_distance_in_meters } => overview_entries
+                .filter(|entry| entry.is_player)
+                .filter(|entry| !is_ignored_player(entry))
+                .filter(|entry| {
+                    entry
+                        .object_distance_in_meters
+                        .is_some_and(|distance| distance <= *max_distance_in_meters)
+                })
+                .map(|entry| AlertEvent {
+                    rule_id: rule.id.clone(),
+                    kind: AlertKind::HostilePlayer,
+                    subject: entry.object_name.clone(),
+                    distance_in_meters: entry.object_distance_in_meters,
+                    message: format!(
+                        "Player {} ({}) at {} m",
+                        entry.object_name.as_deref().unwrap_or("?"),
+                        entry.object_type.as_deref().unwrap_or("?"),
+                        entry.object_distance_in_meters.unwrap_or_default()
+                    ),
+                })
+                .collect(),
+            AlertCondition::NonWhitelistedPlayerClosing { max_time_to_contact_in_seconds } => overview_entries
+                .filter(|entry| entry.is_player)
+                .filter(|entry| !is_ignored_player(entry))
+                .filter_map(|entry| {
+                    let time_to_contact = entry.time_to_contact_in_seconds?;
+                    if time_to_contact > *max_time_to_contact_in_seconds {
+                        return None;
+                    }
+
+                    Some(AlertEvent {
+                        rule_id: rule.id.clone(),
+                        kind: AlertKind::HostilePlayerClosing,
+                        subject: entry.object_name.clone(),
+                        distance_in_meters: entry.object_distance_in_meters,
+                        message: format!(
+                            "Player {} closing at {:.0} m/s, contact in {:.0} s",
+                            entry.object_name.as_deref().unwrap_or("?"),
+                            entry.closing_speed_in_meters_per_second.unwrap_or_default(),
+                            time_to_contact
+                        ),
+                    })
+                })
+                .collect(),
+            AlertCondition::CombatProbesDetected => {
+                let combat_probe_text = t!("combat_scanner_probe").to_lowercase();
+
+                let in_probe_scanner = general_window.probe_scanner.iter().flat_map(|scanner| {
+                    scanner
+                        .entries
+                        .iter()
+                        .filter(|entry| {
+                            entry.name.to_lowercase().contains(&combat_probe_text)
+                                || entry.type_emplacement.to_lowercase().contains(&combat_probe_text)
+                        })
+                        .map(|entry| entry.distance)
+                });
+
+                let in_directional_scanner = general_window.directional_scanner.iter().flat_map(|scanner| {
+                    scanner
+                        .entries
+                        .iter()
+                        .filter(|entry| entry.ship_type.to_lowercase().contains(&combat_probe_text))
+                        .map(|entry| entry.distance)
+                });
+
+                let distances = in_probe_scanner.chain(in_directional_scanner).collect::<Vec<_>>();
+
+                if distances.is_empty() {
+                    return Vec::new();
+                }
+
+                vec![AlertEvent {
+                    rule_id: rule.id.clone(),
+                    kind: AlertKind::CombatProbes,
+                    subject: None,
+                    distance_in_meters: distances.into_iter().flatten().min(),
+                    message: "Combat probes on scan".to_string(),
+                }]
+            }
+            AlertCondition::WarpDisruptingMe => overview_entries
+                .filter(|entry| entry.common_indications.is_warp_disrupting_me)
+                .map(|entry| AlertEvent {
+                    rule_id: rule.id.clone(),
+                    kind: AlertKind::WarpDisrupted,
+                    subject: entry.object_name.clone(),
+                    distance_in_meters: entry.object_distance_in_meters,
+                    message: format!(
+                        "{} is warp disrupting me",
+                        entry.object_name.as_deref().unwrap_or("?")
+                    ),
+                })
+                .collect(),
+            AlertCondition::JammingMe => overview_entries
+                .filter(|entry| entry.common_indications.is_jamming_me)
+                .map(|entry| AlertEvent {
+                    rule_id: rule.id.clone(),
+                    kind: AlertKind::Jammed,
+                    subject: entry.object_name.clone(),
+                    distance_in_meters: entry.object_distance_in_meters,
+                    message: format!("{} is jamming me", entry.object_name.as_deref().unwrap_or("?")),
+                })
+                .collect(),
+            AlertCondition::ExpiredExtractors => {
+                if !general_window.has_expired_extractors() {
+                    return Vec::new();
+                }
+
+                let planet_name = general_window
+                    .planetary_colony
+                    .as_ref()
+                    .and_then(|colony| colony.planet_name.clone());
+
+                vec![AlertEvent {
+                    rule_id: rule.id.clone(),
+                    kind: AlertKind::ExpiredExtractors,
+                    message: format!(
+                        "Extractors expired on {}",
+                        planet_name.as_deref().unwrap_or("planet")
+                    ),
+                    subject: planet_name,
+                    distance_in_meters: None,
+                }]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use crate::alerts::alert_config::EffectiveAlertSettings;
+    use crate::alerts::alert_engine::AlertEngine;
+    use crate::alerts::alert_event::AlertKind;
+    use crate::alerts::alert_rule::AlertRule;
+    use crate::eve::ui::models::general_window::GeneralWindow;
+    use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+    use crate::eve::ui::models::planetary_colony::PlanetaryColony;
+    use crate::eve::ui::models::probe_scanner::{ProbeScanner, ProbeScannerEntry};
+    use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+    fn overview_entry(name: &str, distance_in_meters: i32, is_player: bool) -> OverviewWindowEntry {
+        OverviewWindowEntry::for_test(0, name, distance_in_meters, is_player)
+    }
+
+    fn general_window(entries: Vec<OverviewWindowEntry>) -> GeneralWindow {
+        GeneralWindow::with_overview_entries(entries)
+    }
+
+    fn fired_rules(engine: &mut AlertEngine, general_window: &GeneralWindow, now: Instant) -> Vec<String> {
+        engine
+            .evaluate(general_window, now)
+            .into_iter()
+            .map(|event| event.rule_id)
+            .collect()
+    }
+
+    #[test]
+    fn hostile_player_on_grid_fires_only_within_range() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let window = general_window(vec![
+            overview_entry("Near", 20_000, true),
+            overview_entry("Far", 400_000, true),
+            overview_entry("Rat", 5_000, false),
+        ]);
+
+        let events = engine.evaluate(&window, Instant::now());
+
+        let subjects = events
+            .iter()
+            .filter(|event| event.kind == AlertKind::HostilePlayer)
+            .map(|event| event.subject.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, vec!["Near".to_string()]);
+    }
+
+    #[test]
+    fn hostile_player_closing_fires_below_time_to_contact() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let mut closing = overview_entry("Closing", 300_000, true);
+        closing.closing_speed_in_meters_per_second = Some(20_000.0);
+        closing.time_to_contact_in_seconds = Some(15.0);
+        let mut drifting = overview_entry("Drifting", 300_000, true);
+        drifting.time_to_contact_in_seconds = Some(120.0);
+
+        let fired = fired_rules(&mut engine, &general_window(vec![closing, drifting]), Instant::now());
+
+        assert_eq!(fired, vec!["hostile_player_closing".to_string()]);
+    }
+
+    #[test]
+    fn combat_probes_fire_from_probe_scanner() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let mut window = general_window(Vec::new());
+        window.probe_scanner = Some(Arc::new(ProbeScanner {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            entries: vec![ProbeScannerEntry {
+                distance_unformatted: "12 AU".to_string(),
+                distance: None,
+                id: "ABC-123".to_string(),
+                name: t!("combat_scanner_probe").to_string(),
+                signal_strength: String::new(),
+                signal_strength_percent: None,
+                type_emplacement: String::new(),
+            }],
+            probes: Vec::new(),
+            scan_button: None,
+            formation_controls: Vec::new(),
+            range_controls: Vec::new(),
+        }));
+
+        assert_eq!(fired_rules(&mut engine, &window, Instant::now()), vec!["combat_probes".to_string()]);
+    }
+
+    #[test]
+    fn warp_disruption_and_jamming_fire_from_indications() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let mut pointing = overview_entry("Pointing", 400_000, false);
+        pointing.common_indications.is_warp_disrupting_me = true;
+        let mut jamming = overview_entry("Jamming", 400_000, false);
+        jamming.common_indications.is_jamming_me = true;
+
+        let fired = fired_rules(&mut engine, &general_window(vec![pointing, jamming]), Instant::now());
+
+        assert_eq!(fired, vec!["warp_disrupted".to_string(), "jammed".to_string()]);
+    }
+
+    #[test]
+    fn expired_extractors_fire_for_the_open_colony() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let mut window = general_window(Vec::new());
+        window.planetary_colony = Some(Arc::new(PlanetaryColony {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            planet_name: Some("Tama III".to_string()),
+            extractors: Vec::new(),
+            storage_facilities: Vec::new(),
+            launchpad: None,
+            has_expired_extractors: true,
+        }));
+
+        let events = engine.evaluate(&window, Instant::now());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertKind::ExpiredExtractors);
+        assert_eq!(events[0].subject.as_deref(), Some("Tama III"));
+    }
+
+    #[test]
+    fn cooldown_suppresses_until_it_expires() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let window = general_window(vec![overview_entry("Near", 20_000, true)]);
+        let start = Instant::now();
+
+        assert_eq!(fired_rules(&mut engine, &window, start).len(), 1);
+        assert!(fired_rules(&mut engine, &window, start + Duration::from_secs(5)).is_empty());
+        assert_eq!(fired_rules(&mut engine, &window, start + Duration::from_secs(10)).len(), 1);
+    }
+
+    #[test]
+    fn cooldown_is_kept_per_subject() {
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), HashSet::new());
+        let start = Instant::now();
+
+        fired_rules(&mut engine, &general_window(vec![overview_entry("First", 20_000, true)]), start);
+        let window = general_window(vec![
+            overview_entry("First", 20_000, true),
+            overview_entry("Second", 20_000, true),
+        ]);
+        let events = engine.evaluate(&window, start + Duration::from_secs(1));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].subject.as_deref(), Some("Second"));
+    }
+
+    #[test]
+    fn whitelisted_players_are_ignored() {
+        let whitelist = HashSet::from(["Friend".to_string()]);
+        let mut engine = AlertEngine::new(AlertRule::default_rules(), whitelist);
+        let window = general_window(vec![overview_entry("Friend", 20_000, true)]);
+
+        assert!(fired_rules(&mut engine, &window, Instant::now()).is_empty());
+        assert!(engine.hostile_players(&window).is_empty());
+    }
+
+    #[test]
+    fn players_at_or_above_the_standing_threshold_are_ignored() {
+        let mut engine = AlertEngine::new(Vec::new(), HashSet::new());
+        engine.apply_settings(EffectiveAlertSettings {
+            rules: AlertRule::default_rules(),
+            player_whitelist: HashSet::new(),
+            ignore_standing_at_or_above: Some(5.0),
+        });
+        let mut blue = overview_entry("Blue", 20_000, true);
+        blue.cells_texts.insert(t!("standing").to_string(), "10".to_string());
+        let mut neutral = overview_entry("Neutral", 20_000, true);
+        neutral.cells_texts.insert(t!("standing").to_string(), "0".to_string());
+
+        let window = general_window(vec![blue, neutral]);
+
+        assert_eq!(engine.hostile_players(&window), vec!["Neutral".to_string()]);
+        let events = engine.evaluate(&window, Instant::now());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].subject.as_deref(), Some("Neutral"));
+    }
+}
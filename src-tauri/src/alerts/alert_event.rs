@@ -0,0 +1,20 @@
+﻿use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum AlertKind {
+    HostilePlayer,
+    HostilePlayerClosing,
+    CombatProbes,
+    WarpDisrupted,
+    Jammed,
+    ExpiredExtractors,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AlertEvent {
+    pub rule_id: String,
+    pub kind: AlertKind,
+    pub subject: Option<String>,
+    pub distance_in_meters: Option<i32>,
+    pub message: String,
+}
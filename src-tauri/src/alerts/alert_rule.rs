@@ -0,0 +1,57 @@
+﻿use std::time::Duration;
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    NonWhitelistedPlayerInRange { max_distance_in_meters: i32 },
+    NonWhitelistedPlayerClosing { max_time_to_contact_in_seconds: f64 },
+    CombatProbesDetected,
+    WarpDisruptingMe,
+    JammingMe,
+    ExpiredExtractors,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertRule {
+    pub id: String,
+    pub condition: AlertCondition,
+    #[serde(rename = "cooldown_in_seconds", deserialize_with = "deserialize_seconds")]
+    pub cooldown: Duration,
+}
+
+fn deserialize_seconds<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    u64::deserialize(deserializer).map(Duration::from_secs)
+}
+
+impl AlertRule {
+    pub fn new(id: &str, condition: AlertCondition, cooldown: Duration) -> Self {
+        AlertRule {
+            id: id.to_string(),
+            condition,
+            cooldown,
+        }
+    }
+
+    pub fn default_rules() -> Vec<AlertRule> {
+        vec![
+            AlertRule::new(
+                "hostile_player_on_grid",
+                AlertCondition::NonWhitelistedPlayerInRange { max_distance_in_meters: 150_000 },
+                Duration::from_secs(10),
+            ),
+            AlertRule::new(
+                "hostile_player_closing",
+                AlertCondition::NonWhitelistedPlayerClosing { max_time_to_contact_in_seconds: 30.0 },
+                Duration::from_secs(10),
+            ),
+            AlertRule::new("combat_probes", AlertCondition::CombatProbesDetected, Duration::from_secs(30)),
+            AlertRule::new("warp_disrupted", AlertCondition::WarpDisruptingMe, Duration::from_secs(10)),
+            AlertRule::new("jammed", AlertCondition::JammingMe, Duration::from_secs(10)),
+            AlertRule::new("expired_extractors", AlertCondition::ExpiredExtractors, Duration::from_secs(300)),
+        ]
+    }
+}
@@ -0,0 +1,4 @@
+﻿pub mod alert_rule;
+pub mod alert_event;
+pub mod alert_engine;
+pub mod alert_config;
@@ -1,13 +1,6 @@
-﻿use std::cell::OnceCell;
-use std::collections::HashMap;
-use std::rc::Rc;
-use std::sync::{Arc, Mutex, OnceLock, RwLock};
-use std::sync::atomic::AtomicBool;
-use std::thread;
-use log::info;
-use tauri::Window;
-use crate::operations::eve_ui_tracker::EveUiTracker;
+﻿use log::{info, warn};
 use crate::operations::obtain_pid_process::ObtainPidProcess;
+use crate::operations::tracker_supervisor::{MultiClientState, TrackerSupervisor};
 
 #[tauri::command]
 pub fn get_process_ids() -> Vec<u32> {
@@ -15,61 +8,30 @@ pub fn get_process_ids() -> Vec<u32> {
     processes
 }
 
-static WINDOW_INSTANCES: OnceLock<Arc<Mutex<HashMap<u32, Arc<AtomicBool>>>>> = OnceLock::new();
-static WINDOW_TEST: OnceLock<Arc<Mutex<Window>>> = OnceLock::new();
-pub fn init_window_instance(window: Arc<Mutex<Window>>) {
-    WINDOW_INSTANCES.set(Arc::new(Mutex::new(HashMap::new()))).ok();
-    WINDOW_TEST.set(window).ok();
-}
-
 #[tauri::command]
-    pub fn start_tracker(pid: String) -> bool {
-        let process_id = pid.parse::<u32>().ok().unwrap();
-
-        if let Some(instances) = WINDOW_INSTANCES.get() {
-            {
-                let instances_lock = instances.lock().unwrap();
-
-                if instances_lock.contains_key(&process_id) {
-                    return false;
-                }
-            }
-            let test = WINDOW_TEST.get().unwrap();
-
-            let atomic_bool = Arc::new(AtomicBool::new(true));
-            let mut eve_ui_tracker = EveUiTracker::new(Arc::clone(test),atomic_bool.clone());
-
-            {
-                let mut instances_lock = instances.lock().unwrap();
-                instances_lock.insert(process_id, atomic_bool);
-            }
+pub fn start_tracker(pid: String) -> bool {
+    let Ok(process_id) = pid.parse::<u32>() else {
+        warn!("Invalid process id: {:?}", pid);
+        return false;
+    };
 
-            thread::spawn(move || {
-                eve_ui_tracker.start_tracker(process_id);
-                info!("Stopped tracking process {}", process_id);
-            });
-
-            true
-        } else {
-            false
-        }
-    }
+    TrackerSupervisor::global().is_some_and(|supervisor| supervisor.start_tracker(process_id))
+}
 
 #[tauri::command]
 pub fn stop_tracker(pid: String) -> bool {
-    let process_id = pid.parse::<u32>().ok().unwrap();
+    let Ok(process_id) = pid.parse::<u32>() else {
+        warn!("Invalid process id: {:?}", pid);
+        return false;
+    };
     info!("Stopping tracker for process: {:?}", process_id);
 
-    if let Some(instances) = WINDOW_INSTANCES.get() {
-        let mut instances_lock = instances.lock().unwrap();
-        if let Some(atomic_bool) = instances_lock.get(&process_id) {
-            atomic_bool.store(false, std::sync::atomic::Ordering::Relaxed);
-            instances_lock.remove(&process_id);
-            true
-        } else {
-            false
-        }
-    } else {
-        false
-    }
+    TrackerSupervisor::global().is_some_and(|supervisor| supervisor.stop_tracker(process_id))
+}
+
+#[tauri::command]
+pub fn get_multi_client_state() -> Result<MultiClientState, String> {
+    TrackerSupervisor::global()
+        .map(|supervisor| supervisor.state())
+        .ok_or_else(|| "Tracker supervisor is not initialized".to_string())
 }
@@ -0,0 +1,71 @@
+﻿use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::eve::interop::memory::models::memory_read_stats::MemoryReadStats;
+use crate::eve::interop::memory::windows_memory_reader::WindowsMemoryReader;
+use crate::operations::ui_tree_node_extractor::UiTreeNodeExtractor;
+
+const DEFAULT_FRAMES: u32 = 10;
+const UI_TREE_MAX_DEPTH: i32 = 99;
+
+#[derive(Debug, Serialize)]
+pub struct MemoryStatsReport {
+    pub page_cache: bool,
+    pub frames: u32,
+    pub average_frame_ms: f64,
+    pub totals: MemoryReadStats,
+}
+
+/// Handles `memory-stats <pid> <ui root address> [frames]` from the command line. Reads the
+/// UI tree of a running client with the page cache off and then on, and reports both passes.
+pub fn run_memory_stats_command(args: &[String]) -> Result<String, String> {
+    let usage = "usage: memory-stats <pid> <ui root address> [frames]";
+
+    let process_id = args.first().and_then(|pid| pid.parse::<u32>().ok()).ok_or(usage)?;
+    let address = args.get(1).and_then(|address| parse_address(address)).ok_or(usage)?;
+    let frames = match args.get(2) {
+        Some(frames) => frames.parse::<u32>().ok().filter(|frames| *frames > 0).ok_or(usage)?,
+        None => DEFAULT_FRAMES,
+    };
+
+    let reports = [false, true]
+        .into_iter()
+        .map(|page_cache| measure(process_id, address, frames, page_cache))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    serde_json::to_string_pretty(&reports).map_err(|error| error.to_string())
+}
+
+/// Each pass gets a fresh extractor so neither starts with the other's cached objects.
+fn measure(process_id: u32, address: u64, frames: u32, page_cache: bool) -> Result<MemoryStatsReport, String> {
+    let memory_reader = WindowsMemoryReader::new(process_id)
+        .ok_or_else(|| format!("Could not open process {}", process_id))?;
+    let memory_reader = if page_cache { memory_reader.with_page_cache() } else { memory_reader };
+    let ui_tree_node_extractor = UiTreeNodeExtractor::with_memory_reader(memory_reader);
+
+    let mut totals = MemoryReadStats::default();
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..frames {
+        let start = Instant::now();
+        ui_tree_node_extractor
+            .extract_ui_tree_from_address(address, UI_TREE_MAX_DEPTH)
+            .map_err(|error| error.to_string())?;
+        elapsed += start.elapsed();
+
+        totals.add(&ui_tree_node_extractor.memory_read_stats());
+    }
+
+    Ok(MemoryStatsReport {
+        page_cache,
+        frames,
+        average_frame_ms: elapsed.as_secs_f64() * 1000.0 / frames as f64,
+        totals,
+    })
+}
+
+fn parse_address(address: &str) -> Option<u64> {
+    match address.strip_prefix("0x").or_else(|| address.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse::<u64>().ok(),
+    }
+}
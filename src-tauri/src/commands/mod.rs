@@ -1,2 +1,5 @@
 ﻿pub mod get_process_id;
-pub mod process_watcher;
\ No newline at end of file
+pub mod memory_stats;
+pub mod process_watcher;
+pub mod sightings;
+pub mod schema;
\ No newline at end of file
@@ -3,13 +3,13 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 use tauri::{Emitter, Manager};
-use crate::commands::get_process_id::init_window_instance;
 use crate::operations::obtain_pid_process::ObtainPidProcess;
+use crate::operations::tracker_supervisor::TrackerSupervisor;
 
 pub fn process_watcher(app: &tauri::App) {
     let windows =  app.get_window("main").unwrap();
     let windows = Arc::new(Mutex::new(windows));
-    init_window_instance(windows.clone());
+    TrackerSupervisor::init(windows.clone());
     thread::spawn(move || {
         loop {
             let processes = ObtainPidProcess::execute("exefile").unwrap();
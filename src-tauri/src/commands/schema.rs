@@ -0,0 +1,15 @@
+﻿use crate::db::pool::DatabasePool;
+use crate::db::migrator::SchemaVersionReport;
+
+#[tauri::command]
+pub fn get_schema_version() -> Result<SchemaVersionReport, String> {
+    DatabasePool::global()
+        .and_then(|database_pool| database_pool.get())
+        .and_then(|database| database.schema_version())
+        .map_err(|error| error.to_string())
+}
+
+/// Handles `schema-version` from the command line.
+pub fn run_schema_version_command() -> Result<String, String> {
+    serde_json::to_string_pretty(&get_schema_version()?).map_err(|error| error.to_string())
+}
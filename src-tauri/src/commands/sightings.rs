@@ -0,0 +1,51 @@
+﻿use crate::db::pool::{DatabasePool, PooledDatabase};
+use crate::db::sightings::{Sighting, SightingCount};
+
+const DEFAULT_SYSTEMS_LIMIT: u32 = 10;
+
+fn open_database() -> Result<PooledDatabase, String> {
+    DatabasePool::global()
+        .and_then(|database_pool| database_pool.get())
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub fn get_last_seen(pilot_name: String) -> Result<Option<Sighting>, String> {
+    open_database()?
+        .get_last_seen(&pilot_name)
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub fn get_most_frequent_systems(pilot_name: String, limit: Option<u32>) -> Result<Vec<SightingCount>, String> {
+    open_database()?
+        .get_most_frequent_systems(&pilot_name, limit.unwrap_or(DEFAULT_SYSTEMS_LIMIT))
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub fn get_ships_flown(pilot_name: String) -> Result<Vec<SightingCount>, String> {
+    open_database()?
+        .get_ships_flown(&pilot_name)
+        .map_err(|error| error.to_string())
+}
+
+/// Handles `history <last-seen|systems|ships> <pilot name> [limit]` from the command line.
+pub fn run_history_command(args: &[String]) -> Result<String, String> {
+    let usage = "usage: history <last-seen|systems|ships> <pilot name> [limit]";
+
+    let subcommand = args.first().ok_or(usage)?;
+    let pilot_name = args.get(1).ok_or(usage)?.clone();
+
+    let json = match subcommand.as_str() {
+        "last-seen" => serde_json::to_string_pretty(&get_last_seen(pilot_name)?),
+        "systems" => {
+            let limit = args.get(2).and_then(|limit| limit.parse::<u32>().ok());
+            serde_json::to_string_pretty(&get_most_frequent_systems(pilot_name, limit)?)
+        }
+        "ships" => serde_json::to_string_pretty(&get_ships_flown(pilot_name)?),
+        _ => return Err(usage.to_string()),
+    };
+
+    json.map_err(|error| error.to_string())
+}
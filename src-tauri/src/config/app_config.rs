@@ -0,0 +1,93 @@
+﻿use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use log::{error, info};
+
+const APP_DIRECTORY_NAME: &str = "eve-tracker";
+const DATA_DIR_ENV: &str = "EVE_TRACKER_DATA_DIR";
+const DATABASE_FILE_NAME: &str = "eve.db";
+const ALERT_CONFIG_FILE_NAME: &str = "alerts.toml";
+const POLLS_PER_SECOND_ENV: &str = "EVE_TRACKER_POLLS_PER_SECOND";
+const DEFAULT_POLLS_PER_SECOND: u32 = 10;
+const MAX_POLLS_PER_SECOND: u32 = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseLocation {
+    File(PathBuf),
+    InMemory,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub data_dir: PathBuf,
+    pub database: DatabaseLocation,
+    pub alert_config_path: PathBuf,
+    /// Rate of the zone targeted reads. Full reads of the tree never run faster than 2 Hz.
+    pub polls_per_second: u32,
+}
+
+static APP_CONFIG: OnceLock<AppConfig> = OnceLock::new();
+
+impl AppConfig {
+
+    /// Configuration shared by the desktop app and the CLI, resolved once per process.
+    pub fn global() -> &'static AppConfig {
+        APP_CONFIG.get_or_init(AppConfig::resolve)
+    }
+
+    pub fn resolve() -> AppConfig {
+        let data_dir = AppConfig::resolve_data_dir();
+
+        if let Err(error) = fs::create_dir_all(&data_dir) {
+            error!("Could not create data directory {}: {}", data_dir.display(), error);
+        }
+        info!("Using data directory {}", data_dir.display());
+
+        AppConfig {
+            database: DatabaseLocation::File(data_dir.join(DATABASE_FILE_NAME)),
+            alert_config_path: data_dir.join(ALERT_CONFIG_FILE_NAME),
+            data_dir,
+            polls_per_second: AppConfig::resolve_polls_per_second(),
+        }
+    }
+
+    /// `EVE_TRACKER_POLLS_PER_SECOND` when it holds a number between 1 and 30.
+    fn resolve_polls_per_second() -> u32 {
+        let configured = env::var(POLLS_PER_SECOND_ENV).ok();
+
+        match configured.as_deref().map(|value| value.trim().parse::<u32>()) {
+            Some(Ok(polls_per_second)) if (1..=MAX_POLLS_PER_SECOND).contains(&polls_per_second) => polls_per_second,
+            Some(_) => {
+                error!(
+                    "{} must be between 1 and {}, using {}",
+                    POLLS_PER_SECOND_ENV, MAX_POLLS_PER_SECOND, DEFAULT_POLLS_PER_SECOND
+                );
+                DEFAULT_POLLS_PER_SECOND
+            }
+            None => DEFAULT_POLLS_PER_SECOND,
+        }
+    }
+
+    /// `EVE_TRACKER_DATA_DIR` wins, then the platform per-user data directory, then the
+    /// working directory as a last resort.
+    fn resolve_data_dir() -> PathBuf {
+        if let Some(data_dir) = env::var_os(DATA_DIR_ENV).filter(|value| !value.is_empty()) {
+            return PathBuf::from(data_dir);
+        }
+
+        let platform_data_dir = if cfg!(windows) {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+        } else {
+            env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        };
+
+        platform_data_dir
+            .map(|dir| dir.join(APP_DIRECTORY_NAME))
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
@@ -1,58 +1,64 @@
-﻿use rusqlite::{params, Connection, Result};
+﻿use std::fmt;
+use std::time::Duration;
+use rusqlite::Connection;
+
+/// How long a statement waits for a lock held by another connection or process.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub struct Database {
     pub conn: Connection,
 }
 
-impl Database {
-    // Constructor para abrir una conexión con la base de datos
-    pub fn new(db_path: &str) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
-        let db = Database { conn };
-        
-        db.run_migrations()?;
-        Ok(db)
+impl fmt::Debug for Database {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Database").field("path", &self.conn.path()).finish()
     }
-    
-    fn run_migrations(&self) -> Result<()> {
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS migrations (
-                version INTEGER PRIMARY KEY,
-                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-            )",
-            params![],
-        )?;
-        
-        let current_version = self.get_current_version()?;
-        let migrations = vec![
-            (1, "CREATE TABLE IF NOT EXISTS process_info (
-                id INTEGER PRIMARY KEY,
-                memory_address TEXT NOT NULL
-            )"),
-        ];
-
-        for (version, query) in migrations {
-            if version > current_version {
-                println!("Applying migration version {}", version);
-                self.conn.execute(query, params![])?;
-                self.set_migration_version(version)?;
-            }
+}
+
+#[derive(Debug)]
+pub enum DatabaseError {
+    Sqlite(rusqlite::Error),
+    ChecksumMismatch { version: i32, name: String },
+    UnknownVersion(i32),
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::Sqlite(error) => write!(f, "{}", error),
+            DatabaseError::ChecksumMismatch { version, name } => write!(
+                f,
+                "migration {} ({}) was modified after being applied",
+                version, name
+            ),
+            DatabaseError::UnknownVersion(version) => write!(f, "unknown schema version {}", version),
         }
+    }
+}
 
-        Ok(())
+impl From<rusqlite::Error> for DatabaseError {
+    fn from(error: rusqlite::Error) -> Self {
+        DatabaseError::Sqlite(error)
     }
-    
-    fn get_current_version(&self) -> Result<i32> {
-        let mut stmt = self.conn.prepare("SELECT COALESCE(MAX(version), 0) FROM migrations")?;
-        let version: i32 = stmt.query_row(params![], |row| row.get(0))?;
-        Ok(version)
+}
+
+impl Database {
+    // Constructor para abrir una conexión con la base de datos
+    pub fn new(db_path: &str) -> Result<Self, DatabaseError> {
+        Database::from_connection(Connection::open(db_path)?)
+    }
+
+    pub fn from_connection(conn: Connection) -> Result<Self, DatabaseError> {
+        let mut db = Database::from_migrated_connection(conn)?;
+
+        db.migrate_to_latest()?;
+        Ok(db)
     }
-    
-    fn set_migration_version(&self, version: i32) -> Result<()> {
-        self.conn.execute(
-            "INSERT INTO migrations (version) VALUES (?1)",
-            params![version],
-        )?;
-        Ok(())
+
+    /// Wraps a connection to a database whose schema is already migrated.
+    pub fn from_migrated_connection(conn: Connection) -> Result<Self, DatabaseError> {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+
+        Ok(Database { conn })
     }
 }
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS process_info (
+    id INTEGER PRIMARY KEY,
+    memory_address TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_sightings_pilot_seen_at;
+DROP TABLE IF EXISTS sightings;
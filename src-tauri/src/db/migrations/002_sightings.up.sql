@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS sightings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pilot_name TEXT,
+    ship_type TEXT,
+    alliance TEXT,
+    solar_system TEXT,
+    source TEXT NOT NULL,
+    observer TEXT,
+    seen_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sightings_pilot_seen_at ON sightings (pilot_name, seen_at);
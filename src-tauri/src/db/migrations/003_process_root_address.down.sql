@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS process_info (
+    id INTEGER PRIMARY KEY,
+    memory_address TEXT NOT NULL
+);
+DROP TABLE IF EXISTS process_root_address;
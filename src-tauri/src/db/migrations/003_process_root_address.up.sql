@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS process_root_address (
+    process_id INTEGER NOT NULL,
+    start_time INTEGER NOT NULL,
+    executable_path TEXT NOT NULL,
+    client_build TEXT,
+    memory_address TEXT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (process_id, start_time, executable_path)
+);
+DROP TABLE IF EXISTS process_info;
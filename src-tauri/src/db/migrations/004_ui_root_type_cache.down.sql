@@ -0,0 +1 @@
+DROP TABLE IF EXISTS ui_root_type_cache;
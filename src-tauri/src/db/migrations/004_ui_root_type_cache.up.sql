@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS ui_root_type_cache (
+    executable_path TEXT NOT NULL,
+    client_build TEXT NOT NULL DEFAULT '',
+    python_module_base TEXT NOT NULL,
+    type_object_address TEXT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (executable_path, client_build)
+);
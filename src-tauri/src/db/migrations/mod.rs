@@ -0,0 +1,47 @@
+﻿pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+/// Every schema change, oldest first. Applied migrations must never be edited: their
+/// checksum is verified each time the database is opened.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "process_info",
+        up: include_str!("001_process_info.up.sql"),
+        down: include_str!("001_process_info.down.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "sightings",
+        up: include_str!("002_sightings.up.sql"),
+        down: include_str!("002_sightings.down.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "process_root_address",
+        up: include_str!("003_process_root_address.up.sql"),
+        down: include_str!("003_process_root_address.down.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "ui_root_type_cache",
+        up: include_str!("004_ui_root_type_cache.up.sql"),
+        down: include_str!("004_ui_root_type_cache.down.sql"),
+    },
+];
+
+impl Migration {
+    /// FNV-1a over the up script, stable across builds and Rust versions. Carriage returns
+    /// are skipped so a checkout with CRLF line endings yields the same checksum.
+    pub fn checksum(&self) -> String {
+        let hash = self.up.bytes().filter(|byte| *byte != b'\r').fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+        format!("{:016x}", hash)
+    }
+}
@@ -0,0 +1,301 @@
+﻿use log::info;
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
+use serde::Serialize;
+use crate::db::database::{Database, DatabaseError};
+use crate::db::migrations::{Migration, MIGRATIONS};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AppliedMigration {
+    pub version: i32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SchemaVersionReport {
+    pub current_version: i32,
+    pub latest_version: i32,
+    pub applied: Vec<AppliedMigration>,
+    pub pending: Vec<i32>,
+}
+
+impl Database {
+
+    pub fn latest_version() -> i32 {
+        MIGRATIONS.last().map_or(0, |migration| migration.version)
+    }
+
+    pub fn migrate_to_latest(&mut self) -> Result<(), DatabaseError> {
+        self.migrate_to(Database::latest_version())
+    }
+
+    /// Applies or reverts migrations until the schema is at `target_version`, each one in
+    /// its own transaction. `0` reverts everything.
+    pub fn migrate_to(&mut self, target_version: i32) -> Result<(), DatabaseError> {
+        if target_version != 0 && !MIGRATIONS.iter().any(|migration| migration.version == target_version) {
+            return Err(DatabaseError::UnknownVersion(target_version));
+        }
+
+        self.ensure_schema_migrations_table()?;
+        self.verify_checksums()?;
+
+        let current_version = self.get_current_version()?;
+
+        if target_version > current_version {
+            for migration in MIGRATIONS
+                .iter()
+                .filter(|migration| migration.version > current_version && migration.version <= target_version)
+            {
+                self.apply(migration)?;
+            }
+        } else {
+            for migration in MIGRATIONS
+                .iter()
+                .rev()
+                .filter(|migration| migration.version <= current_version && migration.version > target_version)
+            {
+                self.revert(migration)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn schema_version(&self) -> Result<SchemaVersionReport, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version",
+        )?;
+        let applied = stmt
+            .query_map(params![], |row| {
+                Ok(AppliedMigration {
+                    version: row.get(0)?,
+                    name: row.get(1)?,
+                    checksum: row.get(2)?,
+                    applied_at: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let current_version = applied.last().map_or(0, |migration| migration.version);
+        let pending = MIGRATIONS
+            .iter()
+            .filter(|migration| migration.version > current_version)
+            .map(|migration| migration.version)
+            .collect();
+
+        Ok(SchemaVersionReport {
+            current_version,
+            latest_version: Database::latest_version(),
+            applied,
+            pending,
+        })
+    }
+
+    fn apply(&mut self, migration: &Migration) -> Result<(), DatabaseError> {
+        info!("Applying migration {} ({})", migration.version, migration.name);
+
+        // Taking the write lock up front keeps two processes from applying the same migration.
+        let transaction = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        if Database::is_applied(&transaction, migration.version)? {
+            return Ok(());
+        }
+        transaction.execute_batch(migration.up)?;
+        transaction.execute(
+            "INSERT INTO schema_migrations (version, name, checksum) VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.name, migration.checksum()],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn revert(&mut self, migration: &Migration) -> Result<(), DatabaseError> {
+        info!("Reverting migration {} ({})", migration.version, migration.name);
+
+        let transaction = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        if !Database::is_applied(&transaction, migration.version)? {
+            return Ok(());
+        }
+        transaction.execute_batch(migration.down)?;
+        transaction.execute(
+            "DELETE FROM schema_migrations WHERE version = ?1",
+            params![migration.version],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn is_applied(conn: &rusqlite::Connection, version: i32) -> Result<bool, DatabaseError> {
+        let applied = conn
+            .query_row(
+                "SELECT 1 FROM schema_migrations WHERE version = ?1",
+                params![version],
+                |_| Ok(()),
+            )
+            .optional()?;
+
+        Ok(applied.is_some())
+    }
+
+    fn ensure_schema_migrations_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                checksum TEXT NOT NULL,
+                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )",
+            params![],
+        )?;
+
+        self.import_legacy_migrations()
+    }
+
+    /// Databases created before checksums were tracked only have a `migrations` table with
+    /// version numbers. Their versions are carried over with the current checksums.
+    fn import_legacy_migrations(&mut self) -> Result<(), DatabaseError> {
+        let legacy_table: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'migrations'",
+                params![],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if legacy_table.is_none() {
+            return Ok(());
+        }
+
+        let legacy_version: i32 = self
+            .conn
+            .query_row("SELECT COALESCE(MAX(version), 0) FROM migrations", params![], |row| row.get(0))?;
+
+        let transaction = self.conn.transaction()?;
+        for migration in MIGRATIONS.iter().filter(|migration| migration.version <= legacy_version) {
+            transaction.execute(
+                "INSERT OR IGNORE INTO schema_migrations (version, name, checksum) VALUES (?1, ?2, ?3)",
+                params![migration.version, migration.name, migration.checksum()],
+            )?;
+        }
+        transaction.execute("DROP TABLE migrations", params![])?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn verify_checksums(&self) -> Result<(), DatabaseError> {
+        let mut stmt = self.conn.prepare("SELECT version, name, checksum FROM schema_migrations")?;
+        let applied = stmt
+            .query_map(params![], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (version, name, checksum) in applied {
+            let matches = MIGRATIONS
+                .iter()
+                .find(|migration| migration.version == version)
+                .is_some_and(|migration| migration.checksum() == checksum);
+
+            if !matches {
+                return Err(DatabaseError::ChecksumMismatch { version, name });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_current_version(&self) -> Result<i32, DatabaseError> {
+        let version = self.conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            params![],
+            |row| row.get(0),
+        )?;
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{params, Connection};
+    use crate::db::database::{Database, DatabaseError};
+
+    fn empty_database() -> Database {
+        Database { conn: Connection::open_in_memory().unwrap() }
+    }
+
+    fn table_names(database: &Database) -> Vec<String> {
+        let mut stmt = database
+            .conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+            .unwrap();
+        stmt.query_map(params![], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<String>>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn migrates_empty_database_to_latest_and_back() {
+        let mut database = empty_database();
+
+        database.migrate_to_latest().unwrap();
+        let report = database.schema_version().unwrap();
+        assert_eq!(report.current_version, Database::latest_version());
+        assert!(report.pending.is_empty());
+        assert_eq!(table_names(&database), vec!["process_root_address", "schema_migrations", "sightings", "ui_root_type_cache"]);
+
+        database.migrate_to(0).unwrap();
+        let report = database.schema_version().unwrap();
+        assert_eq!(report.current_version, 0);
+        assert!(report.applied.is_empty());
+        assert_eq!(table_names(&database), vec!["schema_migrations"]);
+    }
+
+    #[test]
+    fn every_migration_can_be_reapplied_after_rollback() {
+        let mut database = empty_database();
+
+        for version in 1..=Database::latest_version() {
+            database.migrate_to(version).unwrap();
+            database.migrate_to(version - 1).unwrap();
+            database.migrate_to(version).unwrap();
+        }
+
+        assert_eq!(database.schema_version().unwrap().current_version, Database::latest_version());
+    }
+
+    #[test]
+    fn imports_legacy_migrations_table() {
+        let mut database = empty_database();
+        database
+            .conn
+            .execute_batch(
+                "CREATE TABLE migrations (version INTEGER PRIMARY KEY, applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP);
+                 INSERT INTO migrations (version) VALUES (1);
+                 CREATE TABLE process_info (id INTEGER PRIMARY KEY, memory_address TEXT NOT NULL);",
+            )
+            .unwrap();
+
+        database.migrate_to_latest().unwrap();
+
+        let report = database.schema_version().unwrap();
+        assert_eq!(report.applied.len() as i32, Database::latest_version());
+        assert!(!table_names(&database).contains(&"migrations".to_string()));
+    }
+
+    #[test]
+    fn rejects_modified_migration() {
+        let mut database = empty_database();
+        database.migrate_to_latest().unwrap();
+        database
+            .conn
+            .execute("UPDATE schema_migrations SET checksum = 'edited' WHERE version = 1", params![])
+            .unwrap();
+
+        match database.migrate_to_latest() {
+            Err(DatabaseError::ChecksumMismatch { version, .. }) => assert_eq!(version, 1),
+            other => panic!("expected checksum mismatch, got {:?}", other),
+        }
+    }
+}
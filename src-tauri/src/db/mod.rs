@@ -1,2 +1,7 @@
 ﻿pub mod database;
+pub mod sightings;
+pub mod migrator;
+pub mod pool;
+pub mod models;
+mod migrations;
 mod queries;
\ No newline at end of file
@@ -0,0 +1,17 @@
+﻿use serde::Serialize;
+
+/// What tells two client processes apart even when the OS hands out the same PID again.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ProcessIdentity {
+    pub process_id: u32,
+    pub start_time: u64,
+    pub executable_path: String,
+    pub client_build: Option<String>,
+}
+
+/// UIRoot type object found by an earlier scan of the same client build.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedUiRootType {
+    pub type_object_address: u64,
+    pub python_module_base: u64,
+}
@@ -0,0 +1,174 @@
+﻿use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use rusqlite::{Connection, OpenFlags};
+use crate::config::app_config::{AppConfig, DatabaseLocation};
+use crate::db::database::{Database, DatabaseError};
+
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+static DATABASE_POOL: Mutex<Option<Arc<DatabasePool>>> = Mutex::new(None);
+static IN_MEMORY_POOL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Hands out migrated connections and takes them back once the borrower is done, so
+/// trackers and commands stop opening the database on every call. The schema is migrated
+/// once when the pool is created.
+#[derive(Debug)]
+pub struct DatabasePool {
+    location: DatabaseLocation,
+    memory_uri: Option<String>,
+    idle_connections: Mutex<Vec<Database>>,
+}
+
+pub struct PooledDatabase {
+    database: Option<Database>,
+    pool: Arc<DatabasePool>,
+}
+
+impl DatabasePool {
+
+    /// Created on first use. When opening or migrating fails, the next call tries again.
+    pub fn global() -> Result<Arc<DatabasePool>, DatabaseError> {
+        let mut database_pool = DATABASE_POOL.lock().unwrap();
+
+        if let Some(database_pool) = database_pool.as_ref() {
+            return Ok(Arc::clone(database_pool));
+        }
+
+        let created_pool = DatabasePool::new(AppConfig::global().database.clone())?;
+        *database_pool = Some(Arc::clone(&created_pool));
+        Ok(created_pool)
+    }
+
+    pub fn new(location: DatabaseLocation) -> Result<Arc<DatabasePool>, DatabaseError> {
+        // Every connection of an in-memory pool points at the same shared-cache database.
+        let memory_uri = match location {
+            DatabaseLocation::InMemory => Some(format!(
+                "file:eve-memory-{}?mode=memory&cache=shared",
+                IN_MEMORY_POOL_COUNT.fetch_add(1, Ordering::Relaxed)
+            )),
+            DatabaseLocation::File(_) => None,
+        };
+
+        let database_pool = Arc::new(DatabasePool {
+            location,
+            memory_uri,
+            idle_connections: Mutex::new(Vec::new()),
+        });
+
+        let mut database = database_pool.open()?;
+        database.migrate_to_latest()?;
+        database_pool.release(database);
+
+        Ok(database_pool)
+    }
+
+    pub fn in_memory() -> Result<Arc<DatabasePool>, DatabaseError> {
+        DatabasePool::new(DatabaseLocation::InMemory)
+    }
+
+    pub fn get(self: &Arc<Self>) -> Result<PooledDatabase, DatabaseError> {
+        let idle_database = self.idle_connections.lock().unwrap().pop();
+
+        let database = match idle_database {
+            Some(database) => database,
+            None => self.open()?,
+        };
+
+        Ok(PooledDatabase {
+            database: Some(database),
+            pool: Arc::clone(self),
+        })
+    }
+
+    fn open(&self) -> Result<Database, DatabaseError> {
+        let conn = match (&self.location, &self.memory_uri) {
+            (DatabaseLocation::File(path), _) => Connection::open(path)?,
+            (DatabaseLocation::InMemory, Some(memory_uri)) => Connection::open_with_flags(
+                memory_uri,
+                OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_URI,
+            )?,
+            (DatabaseLocation::InMemory, None) => Connection::open_in_memory()?,
+        };
+
+        Database::from_migrated_connection(conn)
+    }
+
+    fn release(&self, database: Database) {
+        let mut idle_connections = self.idle_connections.lock().unwrap();
+
+        // An in-memory database disappears with its last connection, so one is always kept.
+        if idle_connections.len() < MAX_IDLE_CONNECTIONS || self.location == DatabaseLocation::InMemory {
+            idle_connections.push(database);
+        }
+    }
+}
+
+impl Deref for PooledDatabase {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        self.database.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledDatabase {
+    fn deref_mut(&mut self) -> &mut Database {
+        self.database.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledDatabase {
+    fn drop(&mut self) {
+        if let Some(database) = self.database.take() {
+            self.pool.release(database);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::pool::DatabasePool;
+    use crate::db::sightings::{NewSighting, SightingSource};
+
+    #[test]
+    fn in_memory_pool_shares_one_migrated_database() {
+        let pool = DatabasePool::in_memory().unwrap();
+
+        let mut writer = pool.get().unwrap();
+        let reader = pool.get().unwrap();
+        assert_eq!(reader.schema_version().unwrap().current_version, Database::latest_version());
+
+        writer
+            .add_sightings(&[NewSighting {
+                pilot_name: Some("Pilot".to_string()),
+                ship_type: Some("Sabre".to_string()),
+                alliance: None,
+                solar_system: Some("Tama".to_string()),
+                source: SightingSource::Overview,
+                observer: None,
+                seen_at: 1,
+            }])
+            .unwrap();
+
+        let last_seen = reader.get_last_seen("Pilot").unwrap().unwrap();
+        assert_eq!(last_seen.solar_system.as_deref(), Some("Tama"));
+    }
+
+    #[test]
+    fn in_memory_pools_are_isolated() {
+        let first_pool = DatabasePool::in_memory().unwrap();
+        let second_pool = DatabasePool::in_memory().unwrap();
+
+        first_pool.get().unwrap().conn.execute_batch("CREATE TABLE only_here (id INTEGER)").unwrap();
+
+        let table_count: i64 = second_pool
+            .get()
+            .unwrap()
+            .conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master WHERE name = 'only_here'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(table_count, 0);
+    }
+}
@@ -1,28 +1,109 @@
 ﻿use rusqlite::{params, OptionalExtension, Result};
 use crate::db::database::Database;
+use crate::db::models::{CachedUiRootType, ProcessIdentity};
 
 impl Database {
-    pub fn add_process_info(&self, process_id: u32, memory_address: String) -> Result<()> {
+    pub fn save_root_address(&self, process: &ProcessIdentity, memory_address: u64) -> Result<()> {
         self.conn.execute(
-            "INSERT INTO process_info (id, memory_address) VALUES (?1, ?2)",
-            params![process_id, memory_address],
+            "INSERT OR REPLACE INTO process_root_address
+                (process_id, start_time, executable_path, client_build, memory_address, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)",
+            params![
+                process.process_id,
+                process.start_time as i64,
+                process.executable_path,
+                process.client_build,
+                memory_address.to_string()
+            ],
         )?;
         Ok(())
     }
-    
-    pub fn get_process_info(&self, process_id: u32) -> Result<Option<(u32, String)>> {
-        let mut stmt = self.conn.prepare("SELECT id, memory_address FROM process_info WHERE id = ?1")?;
-        let process_info = stmt.query_row(params![process_id], |row| {
-            Ok((row.get(0)?, row.get(1)?))
-        }).optional()?;
-        Ok(process_info)
+
+    pub fn get_root_address(&self, process: &ProcessIdentity) -> Result<Option<u64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT memory_address FROM process_root_address
+             WHERE process_id = ?1 AND start_time = ?2 AND executable_path = ?3",
+        )?;
+        let memory_address: Option<String> = stmt
+            .query_row(
+                params![process.process_id, process.start_time as i64, process.executable_path],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(memory_address.and_then(|address| address.parse::<u64>().ok()))
+    }
+
+    pub fn delete_root_address(&self, process: &ProcessIdentity) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM process_root_address
+             WHERE process_id = ?1 AND start_time = ?2 AND executable_path = ?3",
+            params![process.process_id, process.start_time as i64, process.executable_path],
+        )?;
+        Ok(())
     }
-    
-    pub fn delete_process_info(&self, process_id: u32) -> Result<()> {
+
+    pub fn save_ui_root_type(&self, process: &ProcessIdentity, cached_type: &CachedUiRootType) -> Result<()> {
         self.conn.execute(
-            "DELETE FROM process_info WHERE id = ?1",
-            params![process_id],
+            "INSERT OR REPLACE INTO ui_root_type_cache
+                (executable_path, client_build, python_module_base, type_object_address, updated_at)
+             VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)",
+            params![
+                process.executable_path,
+                process.client_build.clone().unwrap_or_default(),
+                cached_type.python_module_base.to_string(),
+                cached_type.type_object_address.to_string()
+            ],
         )?;
         Ok(())
     }
+
+    pub fn get_ui_root_type(&self, process: &ProcessIdentity) -> Result<Option<CachedUiRootType>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT python_module_base, type_object_address FROM ui_root_type_cache
+             WHERE executable_path = ?1 AND client_build = ?2",
+        )?;
+        let cached: Option<(String, String)> = stmt
+            .query_row(
+                params![process.executable_path, process.client_build.clone().unwrap_or_default()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        Ok(cached.and_then(|(python_module_base, type_object_address)| {
+            Some(CachedUiRootType {
+                python_module_base: python_module_base.parse().ok()?,
+                type_object_address: type_object_address.parse().ok()?,
+            })
+        }))
+    }
+
+    /// Drops every cached address whose process is no longer running. Returns how many were removed.
+    pub fn delete_stale_root_addresses(&mut self, running_processes: &[ProcessIdentity]) -> Result<usize> {
+        let transaction = self.conn.transaction()?;
+        let stored: Vec<(u32, i64, String)> = {
+            let mut stmt = transaction
+                .prepare("SELECT process_id, start_time, executable_path FROM process_root_address")?;
+            let rows = stmt.query_map(params![], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+
+        let mut removed = 0;
+        for (process_id, start_time, executable_path) in stored {
+            let still_running = running_processes.iter().any(|process| {
+                process.process_id == process_id
+                    && process.start_time as i64 == start_time
+                    && process.executable_path == executable_path
+            });
+
+            if !still_running {
+                removed += transaction.execute(
+                    "DELETE FROM process_root_address
+                     WHERE process_id = ?1 AND start_time = ?2 AND executable_path = ?3",
+                    params![process_id, start_time, executable_path],
+                )?;
+            }
+        }
+        transaction.commit()?;
+        Ok(removed)
+    }
 }
@@ -0,0 +1,124 @@
+﻿use rusqlite::{params, OptionalExtension, Result, Row};
+use serde::Serialize;
+use crate::db::database::Database;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum SightingSource {
+    Overview,
+    Local,
+    DirectionalScanner,
+}
+
+impl SightingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SightingSource::Overview => "overview",
+            SightingSource::Local => "local",
+            SightingSource::DirectionalScanner => "dscan",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NewSighting {
+    /// `None` for d-scan rows, which show the ship but not who flies it.
+    pub pilot_name: Option<String>,
+    pub ship_type: Option<String>,
+    pub alliance: Option<String>,
+    pub solar_system: Option<String>,
+    pub source: SightingSource,
+    pub observer: Option<String>,
+    pub seen_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Sighting {
+    pub pilot_name: String,
+    pub ship_type: Option<String>,
+    pub alliance: Option<String>,
+    pub solar_system: Option<String>,
+    pub source: String,
+    pub observer: Option<String>,
+    pub seen_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SightingCount {
+    pub value: String,
+    pub sightings: i64,
+    pub last_seen_at: i64,
+}
+
+impl Sighting {
+    fn from_row(row: &Row) -> Result<Sighting> {
+        Ok(Sighting {
+            pilot_name: row.get(0)?,
+            ship_type: row.get(1)?,
+            alliance: row.get(2)?,
+            solar_system: row.get(3)?,
+            source: row.get(4)?,
+            observer: row.get(5)?,
+            seen_at: row.get(6)?,
+        })
+    }
+}
+
+impl Database {
+    pub fn add_sightings(&mut self, sightings: &[NewSighting]) -> Result<()> {
+        let transaction = self.conn.transaction()?;
+        {
+            let mut stmt = transaction.prepare(
+                "INSERT INTO sightings (pilot_name, ship_type, alliance, solar_system, source, observer, seen_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for sighting in sightings {
+                stmt.execute(params![
+                    sighting.pilot_name,
+                    sighting.ship_type,
+                    sighting.alliance,
+                    sighting.solar_system,
+                    sighting.source.as_str(),
+                    sighting.observer,
+                    sighting.seen_at,
+                ])?;
+            }
+        }
+        transaction.commit()
+    }
+
+    pub fn get_last_seen(&self, pilot_name: &str) -> Result<Option<Sighting>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pilot_name, ship_type, alliance, solar_system, source, observer, seen_at
+             FROM sightings WHERE pilot_name = ?1 ORDER BY seen_at DESC LIMIT 1",
+        )?;
+        stmt.query_row(params![pilot_name], Sighting::from_row).optional()
+    }
+
+    pub fn get_most_frequent_systems(&self, pilot_name: &str, limit: u32) -> Result<Vec<SightingCount>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT solar_system, COUNT(*), MAX(seen_at) FROM sightings
+             WHERE pilot_name = ?1 AND solar_system IS NOT NULL
+             GROUP BY solar_system ORDER BY COUNT(*) DESC, MAX(seen_at) DESC LIMIT ?2",
+        )?;
+        let counts = stmt.query_map(params![pilot_name, limit], Database::sighting_count_from_row)?;
+        counts.collect()
+    }
+
+    pub fn get_ships_flown(&self, pilot_name: &str) -> Result<Vec<SightingCount>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT ship_type, COUNT(*), MAX(seen_at) FROM sightings
+             WHERE pilot_name = ?1 AND ship_type IS NOT NULL
+             GROUP BY ship_type ORDER BY MAX(seen_at) DESC",
+        )?;
+        let counts = stmt.query_map(params![pilot_name], Database::sighting_count_from_row)?;
+        counts.collect()
+    }
+
+    fn sighting_count_from_row(row: &Row) -> Result<SightingCount> {
+        Ok(SightingCount {
+            value: row.get(0)?,
+            sightings: row.get(1)?,
+            last_seen_at: row.get(2)?,
+        })
+    }
+}
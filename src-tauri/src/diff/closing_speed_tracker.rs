@@ -0,0 +1,144 @@
+﻿use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use crate::eve::ui::models::general_window::GeneralWindow;
+
+const SAMPLE_WINDOW: Duration = Duration::from_secs(5);
+/// Speeds are averaged over at least this span so a single jumpy distance reading can not
+/// flip the closing alerts on and off.
+const MINIMUM_SAMPLE_SPAN: Duration = Duration::from_secs(1);
+
+/// Keeps a short distance history per overview entity and derives how fast it closes in.
+///
+/// Closing speed is positive while the object approaches. Time to contact is only set
+/// for approaching objects.
+#[derive(Debug)]
+pub struct ClosingSpeedTracker {
+    distance_samples: HashMap<u64, VecDeque<(Instant, i32)>>,
+}
+
+impl ClosingSpeedTracker {
+
+    pub fn new() -> Self {
+        ClosingSpeedTracker {
+            distance_samples: HashMap::new(),
+        }
+    }
+
+    /// Must run after `OverviewEntityTracker::assign_entity_ids` on the same frame.
+    pub fn update(&mut self, general_window: &mut GeneralWindow, now: Instant) {
+        let mut seen_entities = HashSet::new();
+
+        for entry in general_window.overview_entries_mut() {
+            let (entity_id, distance) = match (entry.entity_id, entry.object_distance_in_meters) {
+                (Some(entity_id), Some(distance)) => (entity_id, distance),
+                _ => continue,
+            };
+            seen_entities.insert(entity_id);
+
+            let samples = self.distance_samples.entry(entity_id).or_default();
+            samples.push_back((now, distance));
+            while samples
+                .front()
+                .is_some_and(|(time, _)| now.duration_since(*time) > SAMPLE_WINDOW)
+            {
+                samples.pop_front();
+            }
+
+            let (oldest_time, oldest_distance) = samples.front().copied().unwrap();
+            let elapsed = now.duration_since(oldest_time);
+            if elapsed < MINIMUM_SAMPLE_SPAN {
+                continue;
+            }
+
+            let closing_speed = (oldest_distance - distance) as f64 / elapsed.as_secs_f64();
+            entry.closing_speed_in_meters_per_second = Some(closing_speed);
+
+            if closing_speed > 0.0 {
+                entry.time_to_contact_in_seconds = Some(distance as f64 / closing_speed);
+            }
+        }
+
+        self.distance_samples
+            .retain(|entity_id, _| seen_entities.contains(entity_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use crate::diff::closing_speed_tracker::ClosingSpeedTracker;
+    use crate::eve::ui::models::general_window::GeneralWindow;
+    use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+
+    /// Runs one frame with a single entity and returns its (closing speed, time to contact).
+    fn update(tracker: &mut ClosingSpeedTracker, distance_in_meters: i32, now: Instant) -> (Option<f64>, Option<f64>) {
+        let mut entry = OverviewWindowEntry::for_test(0x10, "Alpha", distance_in_meters, true);
+        entry.entity_id = Some(1);
+        let mut general_window = GeneralWindow::with_overview_entries(vec![entry]);
+
+        tracker.update(&mut general_window, now);
+
+        let entry = &general_window.overview_windows[0].entries[0];
+        (entry.closing_speed_in_meters_per_second, entry.time_to_contact_in_seconds)
+    }
+
+    #[test]
+    fn approaching_entity_gets_speed_and_time_to_contact() {
+        let mut tracker = ClosingSpeedTracker::new();
+        let start = Instant::now();
+
+        update(&mut tracker, 10_000, start);
+        let (closing_speed, time_to_contact) = update(&mut tracker, 6_000, start + Duration::from_secs(2));
+
+        assert_eq!(closing_speed, Some(2_000.0));
+        assert_eq!(time_to_contact, Some(3.0));
+    }
+
+    #[test]
+    fn receding_entity_has_no_time_to_contact() {
+        let mut tracker = ClosingSpeedTracker::new();
+        let start = Instant::now();
+
+        update(&mut tracker, 10_000, start);
+        let (closing_speed, time_to_contact) = update(&mut tracker, 14_000, start + Duration::from_secs(2));
+
+        assert_eq!(closing_speed, Some(-2_000.0));
+        assert_eq!(time_to_contact, None);
+    }
+
+    #[test]
+    fn no_speed_until_the_samples_span_a_second() {
+        let mut tracker = ClosingSpeedTracker::new();
+        let start = Instant::now();
+
+        update(&mut tracker, 10_000, start);
+        assert_eq!(update(&mut tracker, 9_000, start + Duration::from_millis(500)), (None, None));
+
+        let (closing_speed, _) = update(&mut tracker, 8_000, start + Duration::from_secs(1));
+        assert_eq!(closing_speed, Some(2_000.0));
+    }
+
+    #[test]
+    fn speed_is_averaged_over_the_sample_window() {
+        let mut tracker = ClosingSpeedTracker::new();
+        let start = Instant::now();
+
+        update(&mut tracker, 100_000, start);
+        update(&mut tracker, 90_000, start + Duration::from_secs(4));
+        // The first sample has aged out, so the speed is averaged over the last four seconds.
+        let (closing_speed, _) = update(&mut tracker, 80_000, start + Duration::from_secs(8));
+
+        assert_eq!(closing_speed, Some(2_500.0));
+    }
+
+    #[test]
+    fn entities_that_leave_the_overview_start_over() {
+        let mut tracker = ClosingSpeedTracker::new();
+        let start = Instant::now();
+
+        update(&mut tracker, 10_000, start);
+        tracker.update(&mut GeneralWindow::with_overview_entries(Vec::new()), start + Duration::from_secs(1));
+
+        assert_eq!(update(&mut tracker, 5_000, start + Duration::from_secs(2)), (None, None));
+    }
+}
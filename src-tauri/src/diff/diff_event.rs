@@ -0,0 +1,31 @@
+﻿use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffSource {
+    Overview,
+    DirectionalScanner,
+    ProbeScanner,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub previous: Value,
+    pub current: Value,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum DiffChange {
+    Appeared,
+    Disappeared,
+    Changed { fields: Vec<FieldChange> },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DiffEvent {
+    pub source: DiffSource,
+    pub key: String,
+    pub name: Option<String>,
+    pub change: DiffChange,
+}
@@ -0,0 +1,245 @@
+﻿use std::collections::HashMap;
+use crate::diff::diff_event::{DiffChange, DiffEvent, DiffSource};
+use crate::diff::frame_snapshot::{DiffSnapshot, FrameSnapshot};
+use crate::eve::ui::models::general_window::GeneralWindow;
+
+/// Compares each parsed frame with the previous one and reports what appeared,
+/// disappeared or changed.
+#[derive(Debug, Default)]
+pub struct FrameDiff {
+    previous: Option<FrameSnapshot>,
+}
+
+impl FrameDiff {
+
+    pub fn new() -> Self {
+        FrameDiff { previous: None }
+    }
+
+    pub fn diff(&mut self, general_window: &GeneralWindow) -> Vec<DiffEvent> {
+        let current = FrameSnapshot::from_general_window(general_window);
+        let previous = self.previous.take().unwrap_or_default();
+
+        let mut events = Vec::new();
+        FrameDiff::diff_entries(DiffSource::Overview, &previous.overview, &current.overview, &mut events);
+        FrameDiff::diff_entries(
+            DiffSource::DirectionalScanner,
+            &previous.directional_scanner,
+            &current.directional_scanner,
+            &mut events,
+        );
+        FrameDiff::diff_entries(DiffSource::ProbeScanner, &previous.probe_scanner, &current.probe_scanner, &mut events);
+
+        self.previous = Some(current);
+        events
+    }
+
+    fn diff_entries<T: DiffSnapshot>(
+        source: DiffSource,
+        previous: &HashMap<String, T>,
+        current: &HashMap<String, T>,
+        events: &mut Vec<DiffEvent>,
+    ) {
+        for (key, current_entry) in current {
+            let change = match previous.get(key) {
+                None => DiffChange::Appeared,
+                Some(previous_entry) => {
+                    let fields = current_entry.changed_fields(previous_entry);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    DiffChange::Changed { fields }
+                }
+            };
+
+            events.push(DiffEvent {
+                source,
+                key: key.clone(),
+                name: current_entry.name(),
+                change,
+            });
+        }
+
+        for (key, previous_entry) in previous {
+            if !current.contains_key(key) {
+                events.push(DiffEvent {
+                    source,
+                    key: key.clone(),
+                    name: previous_entry.name(),
+                    change: DiffChange::Disappeared,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json::json;
+    use crate::diff::diff_event::{DiffChange, DiffEvent, DiffSource, FieldChange};
+    use crate::diff::frame_diff::FrameDiff;
+    use crate::diff::frame_snapshot::ProbeScannerEntrySnapshot;
+    use crate::eve::ui::models::general_window::GeneralWindow;
+    use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+
+    fn overview_frame(entries: Vec<OverviewWindowEntry>) -> GeneralWindow {
+        GeneralWindow::with_overview_entries(entries)
+    }
+
+    fn sorted_by_key(mut events: Vec<DiffEvent>) -> Vec<DiffEvent> {
+        events.sort_by(|first, second| first.key.cmp(&second.key));
+        events
+    }
+
+    fn probe_result(name: &str, signal_strength_percent: f64) -> ProbeScannerEntrySnapshot {
+        ProbeScannerEntrySnapshot {
+            name: name.to_string(),
+            signal_strength_percent: Some(signal_strength_percent),
+            distance: Some(1_000_000),
+        }
+    }
+
+    #[test]
+    fn every_entry_appears_on_the_first_frame() {
+        let mut frame_diff = FrameDiff::new();
+
+        let events = sorted_by_key(frame_diff.diff(&overview_frame(vec![
+            OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true),
+            OverviewWindowEntry::for_test(0x20, "Bravo", 20_000, true),
+        ])));
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].key, "Alpha|Sabre");
+        assert_eq!(events[0].name.as_deref(), Some("Alpha"));
+        assert_eq!(events[0].source, DiffSource::Overview);
+        assert_eq!(events[0].change, DiffChange::Appeared);
+        assert_eq!(events[1].key, "Bravo|Sabre");
+        assert_eq!(events[1].change, DiffChange::Appeared);
+    }
+
+    #[test]
+    fn unchanged_entries_emit_nothing() {
+        let mut frame_diff = FrameDiff::new();
+
+        frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true)]));
+        let events = frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x30, "Alpha", 10_000, true)]));
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn reports_overview_distance_changes() {
+        let mut frame_diff = FrameDiff::new();
+
+        frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true)]));
+        let events = frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x10, "Alpha", 8_000, true)]));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].change,
+            DiffChange::Changed {
+                fields: vec![FieldChange {
+                    field: "distance_in_meters",
+                    previous: json!(10_000),
+                    current: json!(8_000),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn reports_targeting_and_ewar_changes() {
+        let mut frame_diff = FrameDiff::new();
+
+        frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true)]));
+
+        let mut entry = OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true);
+        entry.common_indications.targeting = true;
+        entry.common_indications.is_jamming_me = true;
+        entry.common_indications.is_warp_disrupting_me = true;
+        let events = frame_diff.diff(&overview_frame(vec![entry]));
+
+        let fields = match &events[..] {
+            [DiffEvent { change: DiffChange::Changed { fields }, .. }] => fields,
+            _ => panic!("expected one changed event, got {:?}", events),
+        };
+        let changed = fields.iter().map(|change| change.field).collect::<Vec<_>>();
+        assert_eq!(changed, vec!["targeting", "is_jamming_me", "is_warp_disrupting_me"]);
+        assert!(fields.iter().all(|change| change.previous == json!(false) && change.current == json!(true)));
+    }
+
+    #[test]
+    fn reports_entries_that_left_the_overview() {
+        let mut frame_diff = FrameDiff::new();
+
+        frame_diff.diff(&overview_frame(vec![
+            OverviewWindowEntry::for_test(0x10, "Alpha", 10_000, true),
+            OverviewWindowEntry::for_test(0x20, "Bravo", 20_000, true),
+        ]));
+        let events = frame_diff.diff(&overview_frame(vec![OverviewWindowEntry::for_test(0x20, "Bravo", 20_000, true)]));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].key, "Alpha|Sabre");
+        assert_eq!(events[0].name.as_deref(), Some("Alpha"));
+        assert_eq!(events[0].change, DiffChange::Disappeared);
+    }
+
+    #[test]
+    fn matches_overview_entries_by_entity_id() {
+        let mut frame_diff = FrameDiff::new();
+
+        let mut first = OverviewWindowEntry::for_test(0x10, "Rat", 10_000, false);
+        first.entity_id = Some(7);
+        frame_diff.diff(&overview_frame(vec![first]));
+
+        let mut moved = OverviewWindowEntry::for_test(0x10, "Rat", 9_000, false);
+        moved.entity_id = Some(7);
+        let events = frame_diff.diff(&overview_frame(vec![moved]));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].key, "7");
+        assert!(matches!(events[0].change, DiffChange::Changed { .. }));
+    }
+
+    #[test]
+    fn reports_probe_signal_strength_changes() {
+        let previous = HashMap::from([("ABC-123".to_string(), probe_result("Unknown", 12.5))]);
+        let current = HashMap::from([("ABC-123".to_string(), probe_result("Unknown", 50.0))]);
+
+        let mut events = Vec::new();
+        FrameDiff::diff_entries(DiffSource::ProbeScanner, &previous, &current, &mut events);
+
+        assert_eq!(
+            events,
+            vec![DiffEvent {
+                source: DiffSource::ProbeScanner,
+                key: "ABC-123".to_string(),
+                name: Some("Unknown".to_string()),
+                change: DiffChange::Changed {
+                    fields: vec![FieldChange {
+                        field: "signal_strength_percent",
+                        previous: json!(12.5),
+                        current: json!(50.0),
+                    }],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_probe_results_appearing_and_disappearing() {
+        let previous = HashMap::from([("ABC-123".to_string(), probe_result("Unknown", 12.5))]);
+        let current = HashMap::from([("XYZ-789".to_string(), probe_result("Unknown", 3.0))]);
+
+        let mut events = Vec::new();
+        FrameDiff::diff_entries(DiffSource::ProbeScanner, &previous, &current, &mut events);
+        let events = sorted_by_key(events);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].key, "ABC-123");
+        assert_eq!(events[0].change, DiffChange::Disappeared);
+        assert_eq!(events[1].key, "XYZ-789");
+        assert_eq!(events[1].change, DiffChange::Appeared);
+    }
+}
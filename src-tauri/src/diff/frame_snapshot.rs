@@ -0,0 +1,230 @@
+﻿use std::collections::HashMap;
+use serde_json::Value;
+use crate::diff::diff_event::FieldChange;
+use crate::eve::ui::models::general_window::GeneralWindow;
+
+/// An entry reduced to the fields that are compared between frames.
+pub trait DiffSnapshot {
+    fn name(&self) -> Option<String>;
+    fn changed_fields(&self, previous: &Self) -> Vec<FieldChange>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverviewEntrySnapshot {
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+    pub distance_in_meters: Option<i32>,
+    pub targeting: bool,
+    pub targeted_by_me: bool,
+    pub is_jamming_me: bool,
+    pub is_warp_disrupting_me: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalScannerEntrySnapshot {
+    pub name: String,
+    pub ship_type: String,
+    pub distance: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeScannerEntrySnapshot {
+    pub name: String,
+    pub signal_strength_percent: Option<f64>,
+    pub distance: Option<i32>,
+}
+
+#[derive(Debug, Default)]
+pub struct FrameSnapshot {
+    pub overview: HashMap<String, OverviewEntrySnapshot>,
+    pub directional_scanner: HashMap<String, DirectionalScannerEntrySnapshot>,
+    pub probe_scanner: HashMap<String, ProbeScannerEntrySnapshot>,
+}
+
+impl FrameSnapshot {
+
+    pub fn from_general_window(general_window: &GeneralWindow) -> FrameSnapshot {
+        let overview = FrameSnapshot::keyed(
+            general_window
+                .overview_windows
+                .iter()
+                .flat_map(|overview| overview.entries.iter())
+                .map(|entry| {
+                    let key = match entry.entity_id {
+                        Some(entity_id) => entity_id.to_string(),
+                        None => format!(
+                            "{}|{}",
+                            entry.object_name.as_deref().unwrap_or_default(),
+                            entry.object_type.as_deref().unwrap_or_default()
+                        ),
+                    };
+
+                    (key, OverviewEntrySnapshot {
+                        name: entry.object_name.clone(),
+                        object_type: entry.object_type.clone(),
+                        distance_in_meters: entry.object_distance_in_meters,
+                        targeting: entry.common_indications.targeting,
+                        targeted_by_me: entry.common_indications.targeted_by_me,
+                        is_jamming_me: entry.common_indications.is_jamming_me,
+                        is_warp_disrupting_me: entry.common_indications.is_warp_disrupting_me,
+                    })
+                }),
+        );
+
+        let directional_scanner = FrameSnapshot::keyed(
+            general_window
+                .directional_scanner
+                .iter()
+                .flat_map(|scanner| scanner.entries.iter())
+                .map(|entry| {
+                    (format!("{}|{}", entry.names, entry.ship_type), DirectionalScannerEntrySnapshot {
+                        name: entry.names.clone(),
+                        ship_type: entry.ship_type.clone(),
+                        distance: entry.distance,
+                    })
+                }),
+        );
+
+        let probe_scanner = FrameSnapshot::keyed(
+            general_window
+                .probe_scanner
+                .iter()
+                .flat_map(|scanner| scanner.entries.iter())
+                .map(|entry| {
+                    // Signature ids are unique per system; anonymous results fall back to their name.
+                    let key = if entry.id.is_empty() { entry.name.clone() } else { entry.id.clone() };
+
+                    (key, ProbeScannerEntrySnapshot {
+                        name: entry.name.clone(),
+                        signal_strength_percent: entry.signal_strength_percent,
+                        distance: entry.distance,
+                    })
+                }),
+        );
+
+        FrameSnapshot {
+            overview,
+            directional_scanner,
+            probe_scanner,
+        }
+    }
+
+    /// Repeated keys (e.g. several identical NPCs) get an occurrence suffix so each row is kept.
+    fn keyed<T>(entries: impl Iterator<Item = (String, T)>) -> HashMap<String, T> {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+        entries
+            .map(|(key, snapshot)| {
+                let occurrence = occurrences.entry(key.clone()).or_insert(0);
+                *occurrence += 1;
+
+                if *occurrence == 1 {
+                    (key, snapshot)
+                } else {
+                    (format!("{}#{}", key, occurrence), snapshot)
+                }
+            })
+            .collect()
+    }
+}
+
+fn field_change<T: Into<Value> + PartialEq + Clone>(
+    changes: &mut Vec<FieldChange>,
+    field: &'static str,
+    previous: &T,
+    current: &T,
+) {
+    if previous != current {
+        changes.push(FieldChange {
+            field,
+            previous: previous.clone().into(),
+            current: current.clone().into(),
+        });
+    }
+}
+
+impl DiffSnapshot for OverviewEntrySnapshot {
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn changed_fields(&self, previous: &Self) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        field_change(&mut changes, "distance_in_meters", &previous.distance_in_meters, &self.distance_in_meters);
+        field_change(&mut changes, "targeting", &previous.targeting, &self.targeting);
+        field_change(&mut changes, "targeted_by_me", &previous.targeted_by_me, &self.targeted_by_me);
+        field_change(&mut changes, "is_jamming_me", &previous.is_jamming_me, &self.is_jamming_me);
+        field_change(&mut changes, "is_warp_disrupting_me", &previous.is_warp_disrupting_me, &self.is_warp_disrupting_me);
+
+        changes
+    }
+}
+
+impl DiffSnapshot for DirectionalScannerEntrySnapshot {
+    fn name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+
+    fn changed_fields(&self, previous: &Self) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        field_change(&mut changes, "distance", &previous.distance, &self.distance);
+
+        changes
+    }
+}
+
+impl DiffSnapshot for ProbeScannerEntrySnapshot {
+    fn name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+
+    fn changed_fields(&self, previous: &Self) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        field_change(&mut changes, "signal_strength_percent", &previous.signal_strength_percent, &self.signal_strength_percent);
+        field_change(&mut changes, "distance", &previous.distance, &self.distance);
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::diff::frame_snapshot::{DiffSnapshot, DirectionalScannerEntrySnapshot, FrameSnapshot};
+
+    #[test]
+    fn repeated_keys_get_an_occurrence_suffix() {
+        let keyed = FrameSnapshot::keyed(
+            vec![("Rat".to_string(), 1), ("Rat".to_string(), 2), ("Boss".to_string(), 3)].into_iter(),
+        );
+
+        assert_eq!(keyed.len(), 3);
+        assert_eq!(keyed["Rat"], 1);
+        assert_eq!(keyed["Rat#2"], 2);
+        assert_eq!(keyed["Boss"], 3);
+    }
+
+    #[test]
+    fn directional_scanner_entries_compare_distance_only() {
+        let previous = DirectionalScannerEntrySnapshot {
+            name: "Alpha's Sabre".to_string(),
+            ship_type: "Sabre".to_string(),
+            distance: Some(5_000_000),
+        };
+        let current = DirectionalScannerEntrySnapshot {
+            distance: Some(2_000_000),
+            ..previous.clone()
+        };
+
+        let changes = current.changed_fields(&previous);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "distance");
+        assert_eq!(changes[0].previous, json!(5_000_000));
+        assert_eq!(changes[0].current, json!(2_000_000));
+        assert!(previous.changed_fields(&previous).is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+﻿pub mod diff_event;
+pub mod frame_snapshot;
+pub mod frame_diff;
+pub mod overview_entity_tracker;
+pub mod closing_speed_tracker;
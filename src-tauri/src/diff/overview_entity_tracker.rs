@@ -0,0 +1,171 @@
+﻿use std::collections::{HashMap, HashSet};
+use crate::eve::ui::models::general_window::GeneralWindow;
+use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+
+#[derive(Debug, Clone)]
+struct KnownEntity {
+    entity_id: u64,
+    object_address: u64,
+    name: Option<String>,
+    object_type: Option<String>,
+}
+
+/// Assigns every overview row a stable `entity_id` so one object can be followed while it
+/// moves up and down the list.
+///
+/// A row keeps its id when its `OverviewScrollEntry` address and name/type are unchanged. When
+/// the client recreates a row, the id is carried over by name/type; when it recycles a row for
+/// another object (same address, different name/type), a new id is issued.
+#[derive(Debug)]
+pub struct OverviewEntityTracker {
+    known_entities: Vec<KnownEntity>,
+    next_entity_id: u64,
+}
+
+impl OverviewEntityTracker {
+
+    pub fn new() -> Self {
+        OverviewEntityTracker {
+            known_entities: Vec::new(),
+            next_entity_id: 1,
+        }
+    }
+
+    pub fn assign_entity_ids(&mut self, general_window: &mut GeneralWindow) {
+        let mut entries = general_window.overview_entries_mut().collect::<Vec<_>>();
+
+        let mut claimed_ids = HashSet::new();
+        let mut assigned: HashMap<usize, u64> = HashMap::new();
+
+        // Exact matches first so a recreated row can not steal the id of an untouched one.
+        for (index, entry) in entries.iter().enumerate() {
+            let exact_match = self.known_entities.iter().find(|known| {
+                known.object_address == entry.object_address
+                    && OverviewEntityTracker::same_object(known, entry)
+                    && !claimed_ids.contains(&known.entity_id)
+            });
+
+            if let Some(known) = exact_match {
+                claimed_ids.insert(known.entity_id);
+                assigned.insert(index, known.entity_id);
+            }
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            if assigned.contains_key(&index) {
+                continue;
+            }
+
+            let entity_id = match self.known_entities.iter().find(|known| {
+                OverviewEntityTracker::same_object(known, entry) && !claimed_ids.contains(&known.entity_id)
+            }) {
+                Some(known) => known.entity_id,
+                None => {
+                    let entity_id = self.next_entity_id;
+                    self.next_entity_id += 1;
+                    entity_id
+                }
+            };
+
+            claimed_ids.insert(entity_id);
+            assigned.insert(index, entity_id);
+        }
+
+        self.known_entities = entries
+            .iter_mut()
+            .enumerate()
+            .map(|(index, entry)| {
+                let entity_id = assigned[&index];
+                entry.entity_id = Some(entity_id);
+
+                KnownEntity {
+                    entity_id,
+                    object_address: entry.object_address,
+                    name: entry.object_name.clone(),
+                    object_type: entry.object_type.clone(),
+                }
+            })
+            .collect();
+    }
+
+    fn same_object(known: &KnownEntity, entry: &OverviewWindowEntry) -> bool {
+        known.name == entry.object_name && known.object_type == entry.object_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::overview_entity_tracker::OverviewEntityTracker;
+    use crate::eve::ui::models::general_window::GeneralWindow;
+    use crate::eve::ui::models::overview_window::OverviewWindowEntry;
+
+    fn assign(tracker: &mut OverviewEntityTracker, rows: &[(u64, &str)]) -> Vec<Option<u64>> {
+        let mut general_window = GeneralWindow::with_overview_entries(
+            rows.iter()
+                .map(|(address, name)| OverviewWindowEntry::for_test(*address, name, 10_000, true))
+                .collect(),
+        );
+
+        tracker.assign_entity_ids(&mut general_window);
+
+        general_window
+            .overview_windows[0]
+            .entries
+            .iter()
+            .map(|entry| entry.entity_id)
+            .collect()
+    }
+
+    #[test]
+    fn untouched_rows_keep_their_id_when_reordered() {
+        let mut tracker = OverviewEntityTracker::new();
+
+        let first = assign(&mut tracker, &[(0x10, "Alpha"), (0x20, "Bravo")]);
+        let second = assign(&mut tracker, &[(0x20, "Bravo"), (0x10, "Alpha")]);
+
+        assert_eq!(first, vec![Some(1), Some(2)]);
+        assert_eq!(second, vec![Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn recreated_rows_carry_their_id_over_by_name() {
+        let mut tracker = OverviewEntityTracker::new();
+
+        assign(&mut tracker, &[(0x10, "Alpha"), (0x20, "Bravo")]);
+        let second = assign(&mut tracker, &[(0x30, "Bravo"), (0x40, "Alpha")]);
+
+        assert_eq!(second, vec![Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn recycled_rows_get_a_new_id() {
+        let mut tracker = OverviewEntityTracker::new();
+
+        assign(&mut tracker, &[(0x10, "Alpha")]);
+        let second = assign(&mut tracker, &[(0x10, "Charlie")]);
+
+        assert_eq!(second, vec![Some(2)]);
+    }
+
+    #[test]
+    fn rows_with_the_same_name_keep_distinct_ids() {
+        let mut tracker = OverviewEntityTracker::new();
+
+        let first = assign(&mut tracker, &[(0x10, "Rat"), (0x20, "Rat")]);
+        let second = assign(&mut tracker, &[(0x20, "Rat"), (0x10, "Rat"), (0x30, "Rat")]);
+
+        assert_eq!(first, vec![Some(1), Some(2)]);
+        assert_eq!(second, vec![Some(2), Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn ids_are_not_reused_after_an_object_leaves() {
+        let mut tracker = OverviewEntityTracker::new();
+
+        assign(&mut tracker, &[(0x10, "Alpha")]);
+        assign(&mut tracker, &[]);
+        let third = assign(&mut tracker, &[(0x10, "Alpha")]);
+
+        assert_eq!(third, vec![Some(2)]);
+    }
+}
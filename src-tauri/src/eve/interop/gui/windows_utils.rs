@@ -1,9 +1,10 @@
-﻿use windows::Win32::Foundation::{BOOL, HWND, LPARAM, WPARAM};
-use windows::Win32::UI::WindowsAndMessaging::{EnumWindows, GetWindowThreadProcessId, PostMessageA, WM_KEYDOWN, WM_KEYUP};
+﻿use windows::Win32::Foundation::{BOOL, HWND, LPARAM, RECT, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{EnumWindows, GetClientRect, GetWindow, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible, PostMessageA, GW_OWNER, WM_KEYDOWN, WM_KEYUP};
 
 struct EnumWindowsData {
     process_id: u32,
-    windows_found: *mut Vec<HWND>
+    windows_found: *mut Vec<HWND>,
+    stop_at_first_window: bool,
 }
 pub struct WindowsUtils {}
 
@@ -16,6 +17,7 @@ impl WindowsUtils {
         let mut data = EnumWindowsData {
             process_id,
             windows_found: &mut windows_found as *mut _,
+            stop_at_first_window: true,
         };
         unsafe {
             let _ = EnumWindows(Some(enum_windows_callback), LPARAM(&mut data as *mut EnumWindowsData as isize));
@@ -24,7 +26,49 @@ impl WindowsUtils {
         windows_found
         
     }
+
+    /// The visible top-level window of the process without an owner, i.e. the game window
+    /// rather than a hidden helper or a dialog that happens to be enumerated first.
+    pub fn get_main_window(process_id: u32) -> Option<HWND> {
+        let mut windows_found: Vec<HWND> = Vec::new();
+
+        let mut data = EnumWindowsData {
+            process_id,
+            windows_found: &mut windows_found as *mut _,
+            stop_at_first_window: false,
+        };
+        unsafe {
+            let _ = EnumWindows(Some(enum_windows_callback), LPARAM(&mut data as *mut EnumWindowsData as isize));
+        }
+
+        windows_found.into_iter().find(|&hwnd| unsafe {
+            IsWindowVisible(hwnd).as_bool() && GetWindow(hwnd, GW_OWNER).is_err()
+        })
+    }
     
+    /// Width and height of the client area of the main window of the process.
+    pub fn get_client_size(process_id: u32) -> Option<(i32, i32)> {
+        let hwnd = WindowsUtils::get_main_window(process_id)?;
+        let mut rect = RECT::default();
+
+        unsafe { GetClientRect(hwnd, &mut rect) }.ok()?;
+
+        Some((rect.right - rect.left, rect.bottom - rect.top))
+    }
+
+    /// Caption of the main window of the process.
+    pub fn get_window_title(process_id: u32) -> Option<String> {
+        let hwnd = WindowsUtils::get_main_window(process_id)?;
+        let mut title_buffer = [0u16; 256];
+
+        let title_length = unsafe { GetWindowTextW(hwnd, &mut title_buffer) };
+        if title_length <= 0 {
+            return None;
+        }
+
+        Some(String::from_utf16_lossy(&title_buffer[..title_length as usize]))
+    }
+
     pub fn simulate_key_press(hwnd: HWND, key: u32) {
         unsafe {
             let _ = PostMessageA(hwnd, WM_KEYDOWN, WPARAM(key as usize), LPARAM(0isize));
@@ -43,8 +87,9 @@ unsafe extern "system" fn enum_windows_callback(hwnd: HWND, l_param: LPARAM) ->
     if window_process_id == data.process_id {
         (*data.windows_found).push(hwnd);
         
-        
-        return BOOL(0);
+        if data.stop_at_first_window {
+            return BOOL(0);
+        }
     }
     
     BOOL(1)
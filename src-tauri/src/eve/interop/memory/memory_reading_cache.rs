@@ -1,54 +1,171 @@
-﻿use std::cell::RefCell;
-use std::collections::HashMap;
+﻿use std::collections::HashMap;
 use std::hash::Hash;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use serde::Serialize;
+
+/// Entries not validated for this many frames are dropped so the cache does not grow forever.
+const STALE_AFTER_FRAMES: u64 = 100;
+
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+}
+
+impl CacheCounters {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+
+    fn add(&self, other: &CacheCounters) -> CacheCounters {
+        CacheCounters {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            invalidations: self.invalidations + other.invalidations,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MemoryReadingCacheStats {
+    pub type_names: CacheCounters,
+    pub type_objects: CacheCounters,
+    pub strings: CacheCounters,
+    pub dict_entry_values: CacheCounters,
+    pub hit_rate: f64,
+}
+
+/// A cached value plus the tag it was read with. The tag is reread from memory
+/// once per frame and the value is only trusted while the tag stays the same.
+struct ValidatedEntry<T, V> {
+    tag: T,
+    value: V,
+    validated_in_frame: u64,
+}
 
 pub struct MemoryReadingCache {
-    python_type_name_from_python_object_address: Rc<RefCell<HashMap<u64, String>>>,
-    python_string_value_max_length_4000: Rc<RefCell<HashMap<u64, String>>>,
-    dict_entry_value_representation: Rc<RefCell<HashMap<u64, Rc<Box<dyn std::any::Any>>>>>,
+    frame: AtomicU64,
+    python_type_name_from_python_object_address: Mutex<HashMap<u64, ValidatedEntry<u64, String>>>,
+    python_type_name_from_type_object_address: Mutex<HashMap<u64, String>>,
+    python_string_value_max_length_4000: Mutex<HashMap<u64, ValidatedEntry<Vec<u8>, String>>>,
+    dict_entry_value_representation: Arc<Mutex<HashMap<u64, Arc<Box<dyn std::any::Any + Send + Sync>>>>>,
+    type_name_counters: Mutex<CacheCounters>,
+    type_object_counters: Mutex<CacheCounters>,
+    string_counters: Mutex<CacheCounters>,
+    dict_entry_value_counters: Mutex<CacheCounters>,
 }
 
 
 impl MemoryReadingCache {
     pub fn new() -> Self {
         Self {
-            python_type_name_from_python_object_address: Rc::new(RefCell::new(HashMap::new())),
-            python_string_value_max_length_4000: Rc::new(RefCell::new(HashMap::new())),
-            dict_entry_value_representation: Rc::new(RefCell::new(HashMap::new())),
+            frame: AtomicU64::new(0),
+            python_type_name_from_python_object_address: Mutex::new(HashMap::new()),
+            python_type_name_from_type_object_address: Mutex::new(HashMap::new()),
+            python_string_value_max_length_4000: Mutex::new(HashMap::new()),
+            dict_entry_value_representation: Arc::new(Mutex::new(HashMap::new())),
+            type_name_counters: Mutex::new(CacheCounters::default()),
+            type_object_counters: Mutex::new(CacheCounters::default()),
+            string_counters: Mutex::new(CacheCounters::default()),
+            dict_entry_value_counters: Mutex::new(CacheCounters::default()),
         }
     }
 
-    pub fn clear(&self) {
-        self.python_string_value_max_length_4000.borrow_mut().clear();
-        self.python_type_name_from_python_object_address.borrow_mut().clear();
-        self.dict_entry_value_representation.borrow_mut().clear();
+    /// Starts a new frame. Type names and strings survive and get revalidated lazily,
+    /// dict entry values are mutable (regions, texts, flags) so they are dropped.
+    pub fn begin_frame(&self) {
+        let frame = self.frame.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.dict_entry_value_representation.lock().unwrap().clear();
+
+        if frame % STALE_AFTER_FRAMES == 0 {
+            let oldest_frame = frame - STALE_AFTER_FRAMES;
+            self.python_type_name_from_python_object_address
+                .lock().unwrap()
+                .retain(|_, entry| entry.validated_in_frame >= oldest_frame);
+            self.python_string_value_max_length_4000
+                .lock().unwrap()
+                .retain(|_, entry| entry.validated_in_frame >= oldest_frame);
+        }
     }
 
-    pub fn get_python_type_name_from_python_object_address<F>(&self, address: u64, get_fresh: F) -> Option<String>
+    pub fn stats(&self) -> MemoryReadingCacheStats {
+        let type_names = *self.type_name_counters.lock().unwrap();
+        let type_objects = *self.type_object_counters.lock().unwrap();
+        let strings = *self.string_counters.lock().unwrap();
+        let dict_entry_values = *self.dict_entry_value_counters.lock().unwrap();
+
+        let total = type_names.add(&type_objects).add(&strings).add(&dict_entry_values);
+
+        MemoryReadingCacheStats {
+            type_names,
+            type_objects,
+            strings,
+            dict_entry_values,
+            hit_rate: total.hit_rate(),
+        }
+    }
+
+    /// Tagged with the address of the object's type object, the name is reused while it does not change.
+    pub fn get_python_type_name_from_python_object_address<R, F>(&self, address: u64, read_type_object_address: R, get_fresh: F) -> Option<String>
     where
-        F: FnOnce() -> Result<String, &'static str>,
+        R: FnOnce() -> Result<u64, &'static str>,
+        F: FnOnce(&u64) -> Result<String, &'static str>,
     {
-        self.get_from_cache_or_update(&self.python_type_name_from_python_object_address, address, get_fresh)
+        self.get_from_validated_cache_or_update(
+            &self.python_type_name_from_python_object_address,
+            &self.type_name_counters,
+            address,
+            read_type_object_address,
+            get_fresh,
+        )
     }
 
-    pub fn get_python_string_value_max_length_4000<F>(&self, address: u64, get_fresh: F) -> Option<String>
+    /// Type objects live as long as the process, so their names never need revalidation.
+    pub fn get_python_type_name_from_type_object_address<F>(&self, type_object_address: u64, get_fresh: F) -> Option<String>
     where
         F: FnOnce() -> Result<String, &'static str>,
     {
-        self.get_from_cache_or_update(&self.python_string_value_max_length_4000, address, get_fresh)
+        self.get_from_cache_or_update(
+            &self.python_type_name_from_type_object_address,
+            &self.type_object_counters,
+            type_object_address,
+            get_fresh,
+        )
     }
 
-    pub fn get_dict_entry_value_representation<F>(&self, address: u64, get_fresh: F) -> Option<Rc<Box<dyn std::any::Any>>>
+    /// Tagged with the string object header (type, length and hash), which changes when the address gets reused.
+    pub fn get_python_string_value_max_length_4000<R, F>(&self, address: u64, read_header: R, get_fresh: F) -> Option<String>
     where
-        F: FnOnce() -> Result<Rc<Box<dyn std::any::Any>>, &'static str>,
+        R: FnOnce() -> Result<Vec<u8>, &'static str>,
+        F: FnOnce(&Vec<u8>) -> Result<String, &'static str>,
     {
-        self.get_from_cache_or_update(&self.dict_entry_value_representation, address, get_fresh)
+        self.get_from_validated_cache_or_update(
+            &self.python_string_value_max_length_4000,
+            &self.string_counters,
+            address,
+            read_header,
+            get_fresh,
+        )
+    }
+
+    pub fn get_dict_entry_value_representation<F>(&self, address: u64, get_fresh: F) -> Option<Arc<Box<dyn std::any::Any + Send + Sync>>>
+    where
+        F: FnOnce() -> Result<Arc<Box<dyn std::any::Any + Send + Sync>>, &'static str>,
+    {
+        self.get_from_cache_or_update(&self.dict_entry_value_representation, &self.dict_entry_value_counters, address, get_fresh)
     }
 
     fn get_from_cache_or_update<K, V, F>(
         &self,
-        cache: &Rc<RefCell<HashMap<K, V>>>,
+        cache: &Mutex<HashMap<K, V>>,
+        counters: &Mutex<CacheCounters>,
         key: K,
         get_fresh: F,
     ) -> Option<V>
@@ -58,21 +175,86 @@ impl MemoryReadingCache {
         F: FnOnce() -> Result<V, &'static str>,
     {
         {
-            let cache_lock = cache.borrow();
+            let cache_lock = cache.lock().unwrap();
             if let Some(from_cache) = cache_lock.get(&key) {
+                MemoryReadingCache::count(counters, |counters| counters.hits += 1);
                 return Some(from_cache.clone());
             }
         }
 
+        MemoryReadingCache::count(counters, |counters| counters.misses += 1);
         let fresh = get_fresh();
 
         if fresh.is_ok() {
             let result = fresh.unwrap();
-            let mut cache_lock = cache.borrow_mut();
+            let mut cache_lock = cache.lock().unwrap();
             cache_lock.insert(key, result.clone());
             return Some(result);
         }
 
         None
     }
+
+    fn get_from_validated_cache_or_update<T, V, R, F>(
+        &self,
+        cache: &Mutex<HashMap<u64, ValidatedEntry<T, V>>>,
+        counters: &Mutex<CacheCounters>,
+        key: u64,
+        read_tag: R,
+        get_fresh: F,
+    ) -> Option<V>
+    where
+        T: PartialEq,
+        V: Clone,
+        R: FnOnce() -> Result<T, &'static str>,
+        F: FnOnce(&T) -> Result<V, &'static str>,
+    {
+        let frame = self.frame.load(Ordering::Relaxed);
+
+        {
+            let cache_lock = cache.lock().unwrap();
+            if let Some(entry) = cache_lock.get(&key) {
+                if entry.validated_in_frame == frame {
+                    MemoryReadingCache::count(counters, |counters| counters.hits += 1);
+                    return Some(entry.value.clone());
+                }
+            }
+        }
+
+        let tag = match read_tag() {
+            Ok(tag) => tag,
+            Err(_) => {
+                if cache.lock().unwrap().remove(&key).is_some() {
+                    MemoryReadingCache::count(counters, |counters| counters.invalidations += 1);
+                }
+                return None;
+            }
+        };
+
+        {
+            let mut cache_lock = cache.lock().unwrap();
+            if let Some(entry) = cache_lock.get_mut(&key) {
+                if entry.tag == tag {
+                    entry.validated_in_frame = frame;
+                    MemoryReadingCache::count(counters, |counters| counters.hits += 1);
+                    return Some(entry.value.clone());
+                }
+                cache_lock.remove(&key);
+                MemoryReadingCache::count(counters, |counters| counters.invalidations += 1);
+            }
+        }
+
+        MemoryReadingCache::count(counters, |counters| counters.misses += 1);
+
+        let value = get_fresh(&tag).ok()?;
+        cache.lock().unwrap().insert(key, ValidatedEntry { tag, value: value.clone(), validated_in_frame: frame });
+        Some(value)
+    }
+
+    fn count<U>(counters: &Mutex<CacheCounters>, update: U)
+    where
+        U: FnOnce(&mut CacheCounters),
+    {
+        update(&mut counters.lock().unwrap());
+    }
 }
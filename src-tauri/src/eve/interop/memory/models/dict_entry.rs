@@ -1,7 +1,7 @@
-﻿use std::rc::Rc;
+﻿use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct DictEntry {
     pub key: String,
-    pub value: Rc<Box<dyn std::any::Any>>, // Rust doesn't have a direct equivalent to C#'s `object`, so we use a trait object
+    pub value: Arc<Box<dyn std::any::Any + Send + Sync>>, // Rust doesn't have a direct equivalent to C#'s `object`, so we use a trait object
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+﻿use serde::Serialize;
+
+/// Read counters of a `WindowsMemoryReader` with the page cache enabled, covering the reads
+/// since the previous report (one frame for the trackers).
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct MemoryReadStats {
+    pub requested_reads: u64,
+    /// Every `ReadProcessMemory` call, page reads and fallbacks included.
+    pub syscalls: u64,
+    pub page_hits: u64,
+    pub page_misses: u64,
+    /// Small reads that hit an unreadable page and were read directly instead.
+    pub fallback_reads: u64,
+    /// Requested reads minus syscalls. Negative when page reads and fallbacks cost more
+    /// than they saved.
+    pub syscalls_saved: i64,
+}
+
+impl MemoryReadStats {
+    /// Adds the counters of another report, e.g. to total several frames.
+    pub fn add(&mut self, other: &MemoryReadStats) {
+        self.requested_reads += other.requested_reads;
+        self.syscalls += other.syscalls;
+        self.page_hits += other.page_hits;
+        self.page_misses += other.page_misses;
+        self.fallback_reads += other.fallback_reads;
+        self.syscalls_saved += other.syscalls_saved;
+    }
+}
@@ -2,4 +2,5 @@
 pub mod int_wrapper;
 pub mod py_dict_entry;
 pub mod dict_entry_representation;
-pub mod dict_entry;
\ No newline at end of file
+pub mod dict_entry;
+pub mod memory_read_stats;
\ No newline at end of file
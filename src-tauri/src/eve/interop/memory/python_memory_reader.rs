@@ -8,17 +8,17 @@ use crate::eve::interop::memory::models::py_dict_entry::PyDictEntry;
 use crate::eve::interop::memory::utils::MemoryUtils;
 use crate::eve::interop::memory::python_type_extractor::PythonTypeExtractor;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 use log::debug;
 
 pub struct PythonMemoryReader {
-    memory_reader: Rc<WindowsMemoryReader>,
+    memory_reader: Arc<WindowsMemoryReader>,
 }
 
 impl PythonMemoryReader {
-    pub fn new(windows_memory_reader: &Rc<WindowsMemoryReader>) -> Self {
+    pub fn new(windows_memory_reader: &Arc<WindowsMemoryReader>) -> Self {
         Self {
-            memory_reader: Rc::clone(windows_memory_reader),
+            memory_reader: Arc::clone(windows_memory_reader),
         }
     }
 
@@ -31,31 +31,55 @@ impl PythonMemoryReader {
         object_address: u64,
         memory_reading_cache: &MemoryReadingCache,
     ) -> Result<String, &'static str> {
+        let result = memory_reading_cache.get_python_type_name_from_python_object_address(
+            object_address,
+            || self.read_type_object_address(object_address),
+            |&type_object_address| {
+                memory_reading_cache
+                    .get_python_type_name_from_type_object_address(type_object_address, || {
+                        self.get_python_type_name_from_type_object_address(type_object_address)
+                    })
+                    .ok_or_else(|| "Failed to get python type name from type object address")
+            },
+        );
+
+        result.ok_or_else(|| "Failed to get python type name from object address")
+    }
+
+    fn read_type_object_address(&self, object_address: u64) -> Result<u64, &'static str> {
         let python_object_memory_size: usize = 16;
 
         let object_type_offset: usize = 8;
 
-        let result = memory_reading_cache.get_python_type_name_from_python_object_address(
-            object_address,
-            || {
-                let object_memory = self
-                    .memory_reader
-                    .read_bytes(object_address, python_object_memory_size as u64)?;
+        let object_memory = self
+            .memory_reader
+            .read_bytes(object_address, python_object_memory_size as u64)?;
 
-                if object_memory.len() != python_object_memory_size {
-                    return Err("Length is not 16");
-                }
+        if object_memory.len() != python_object_memory_size {
+            return Err("Length is not 16");
+        }
 
-                let type_object_address = u64::from_le_bytes(
-                    object_memory[object_type_offset..object_type_offset + 8]
-                        .try_into()
-                        .unwrap(),
-                );
-                self.get_python_type_name_from_type_object_address(type_object_address)
-            },
-        );
+        Ok(u64::from_le_bytes(
+            object_memory[object_type_offset..object_type_offset + 8]
+                .try_into()
+                .unwrap(),
+        ))
+    }
 
-        result.ok_or_else(|| "Failed to get python type name from object address")
+    /// Type, size, hash and interning state of a string object, everything but the refcount.
+    fn read_python_string_header(&self, string_object_address: u64) -> Result<Vec<u8>, &'static str> {
+        let string_object_memory_size: usize = 32;
+        let string_object_type_offset: usize = 8;
+
+        let string_object_memory = self
+            .memory_reader
+            .read_bytes(string_object_address, string_object_memory_size as u64)?;
+
+        if string_object_memory.len() != string_object_memory_size {
+            return Err("Length is not 32");
+        }
+
+        Ok(string_object_memory[string_object_type_offset..].to_vec())
     }
 
     pub fn get_python_type_name_from_type_object_address(
@@ -142,9 +166,16 @@ impl PythonMemoryReader {
     }
 
     pub fn reading_from_python_type_unicode(&self, address: u64) -> Result<String, &'static str> {
+        self.read_python_unicode_value(address, 4096) // 0x1000 in hex
+    }
+
+    pub fn read_python_unicode_value(
+        &self,
+        address: u64,
+        unicode_string_max_length: u64,
+    ) -> Result<String, &'static str> {
         let python_object_memory_size: usize = 32; // 0x20 in hex
         let unicode_string_length_offset: usize = 16; // 0x10 in hex
-        let unicode_string_max_length: u64 = 4096; // 0x1000 in hex
         let string_bytes_offset: usize = 24; // 0x18 in hex
 
         let python_object_memory = self
@@ -277,9 +308,11 @@ impl PythonMemoryReader {
         cache: &MemoryReadingCache,
     ) -> Result<String, &'static str> {
         let cache_result =
-            cache.get_python_string_value_max_length_4000(str_object_address, || {
-                return self.read_python_string_value(str_object_address, 4000);
-            });
+            cache.get_python_string_value_max_length_4000(
+                str_object_address,
+                || self.read_python_string_header(str_object_address),
+                |_| self.read_python_string_value(str_object_address, 4000),
+            );
 
         cache_result.ok_or_else(|| "Failed to read python string value")
     }
@@ -380,7 +413,7 @@ impl PythonMemoryReader {
         &self,
         value_object_address: u64,
         memory_reading_cache: &MemoryReadingCache,
-    ) -> Rc<Box<dyn std::any::Any>> {
+    ) -> Arc<Box<dyn std::any::Any + Send + Sync>> {
         let result_cache =
             memory_reading_cache.get_dict_entry_value_representation(value_object_address, || {
                 let value_python_type_name = self.get_python_type_name_from_python_object_address(
@@ -390,10 +423,10 @@ impl PythonMemoryReader {
 
                 let value_python_type_name_option = value_python_type_name.as_ref().ok();
 
-                let generic_representation = Rc::new(Box::new(PyDictEntryRepresentation {
+                let generic_representation = Arc::new(Box::new(PyDictEntryRepresentation {
                     address: value_object_address,
                     python_object_type_name: value_python_type_name_option.cloned(),
-                }) as Box<dyn std::any::Any>);
+                }) as Box<dyn std::any::Any + Send + Sync>);
 
                 if (value_python_type_name_option.is_none()) {
                     return Ok(generic_representation);
@@ -411,7 +444,7 @@ impl PythonMemoryReader {
                     return Ok(generic_representation);
                 }
 
-                specialized_representation.map(|value| Rc::new(value))
+                specialized_representation.map(|value| Arc::new(value))
             });
 
         if (result_cache.is_none()) {
@@ -425,21 +458,6 @@ impl PythonMemoryReader {
         object_address: u64,
         memory_reading_cache: &MemoryReadingCache,
     ) -> Result<String, &'static str> {
-        let cache_result = memory_reading_cache.get_python_type_name_from_python_object_address(
-            object_address,
-            || {
-                let object_memory = self.memory_reader.read_bytes(object_address, 0x10)?;
-
-                if object_memory.len() != 0x10 {
-                    return Err("Length is not 0x10");
-                }
-
-                return self.get_python_type_name_from_type_object_address(u64::from_le_bytes(
-                    object_memory[8..].try_into().unwrap(),
-                ));
-            },
-        );
-
-        cache_result.ok_or_else(|| "Failed to get python type name from object address")
+        self.get_python_type_name_from_object_address(object_address, memory_reading_cache)
     }
 }
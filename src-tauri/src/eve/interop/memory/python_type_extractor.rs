@@ -6,7 +6,7 @@ use serde::Serialize;
 use serde_json::{to_value, Value};
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::sync::Arc;
 use crate::eve::ui_tree_node::common::common::{Bunch, ColorComponents};
 use crate::eve::ui_tree_node::models::ui_tree_node::UiTreeNode;
 
@@ -18,7 +18,7 @@ impl PythonTypeExtractor {
         address: u64,
         value_python_type: &str,
         memory_reading_cache: &MemoryReadingCache,
-    ) -> Result<Box<dyn Any>, &'static str> {
+    ) -> Result<Box<dyn Any + Send + Sync>, &'static str> {
         let handler = TYPE_HANDLERS.get(value_python_type);
         if handler.is_none() {
             return Err("Failed to find handler for python type");
@@ -29,8 +29,31 @@ impl PythonTypeExtractor {
     pub fn is_key_of_interest(key: &str) -> bool {
         DICT_ENTRIES_OF_INTEREST_KEYS.contains(key)
     }
+
+    /// Keys holding whole documents (mission briefings, objectives) that outgrow the default string cap.
+    pub fn is_long_text_key(key: &str) -> bool {
+        LONG_TEXT_KEYS.contains(key)
+    }
+
+    pub fn reading_long_text(
+        memory_reader: &PythonMemoryReader,
+        address: u64,
+        memory_reading_cache: &MemoryReadingCache,
+    ) -> Result<Box<dyn Any + Send + Sync>, &'static str> {
+        let python_type = memory_reader.get_python_type_name_from_object_address(address, memory_reading_cache)?;
+
+        let text = match python_type.as_str() {
+            "str" => memory_reader.read_python_string_value(address, LONG_TEXT_MAX_LENGTH as i32)?,
+            "unicode" => memory_reader.read_python_unicode_value(address, LONG_TEXT_MAX_LENGTH)?,
+            _ => return Err("Long text entry is not a string"),
+        };
+
+        Ok(Box::new(text))
+    }
 }
 
+const LONG_TEXT_MAX_LENGTH: u64 = 0x40000;
+
 fn reading_from_python_type_str(
     memory_reader: &PythonMemoryReader,
     address: u64,
@@ -183,7 +206,7 @@ impl<T: Serialize + Any + Send + Sync> SerializeBox for T {
     }
 }
 
-fn serialize_memory_reading_node_to_json(value: Rc<Box<dyn Any>>) -> Result<Value, &'static str> {
+fn serialize_memory_reading_node_to_json(value: Arc<Box<dyn Any + Send + Sync>>) -> Result<Value, &'static str> {
     if let Some(serialize_box) = value.downcast_ref::<Box<dyn SerializeBox>>() {
         serialize_box
             .serialize_to_value()
@@ -198,66 +221,67 @@ type HandlerFn = fn(PythonMemoryReader, u64, MemoryReadingCache) -> Result<dyn A
 lazy_static! {
     static ref TYPE_HANDLERS: HashMap<
         String,
-        fn(&PythonMemoryReader, u64, &MemoryReadingCache) -> Result<Box<dyn Any>, &'static str>,
+        fn(&PythonMemoryReader, u64, &MemoryReadingCache) -> Result<Box<dyn Any + Send + Sync>, &'static str>,
     > = {
         let mut m: HashMap<
             String,
-            fn(&PythonMemoryReader, u64, &MemoryReadingCache) -> Result<Box<dyn Any>, &'static str>,
+            fn(&PythonMemoryReader, u64, &MemoryReadingCache) -> Result<Box<dyn Any + Send + Sync>, &'static str>,
         > = HashMap::new();
         m.insert(
             String::from("str"),
             |mr: &PythonMemoryReader, addr, _cache| {
-                reading_from_python_type_str(mr, addr).map(|value| Box::new(value) as Box<dyn Any>)
+                reading_from_python_type_str(mr, addr).map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("unicode"),
             |mr: &PythonMemoryReader, addr, _cache| {
                 mr.reading_from_python_type_unicode(addr)
-                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("int"),
             |mr: &PythonMemoryReader, addr, _cache| {
                 mr.reading_from_python_type_int(addr)
-                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("bool"),
             |mr: &PythonMemoryReader, addr, _cache| {
                 mr.reading_from_python_type_bool(addr)
-                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("float"),
             |mr: &PythonMemoryReader, addr, _cache| {
                 mr.read_python_float_object_value(addr)
-                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("PyColor"),
             |mr: &PythonMemoryReader, addr, cache| {
-                reading_from_python_type_pycolor(mr, addr, cache).map(|value| value as Box<dyn Any>)
+                reading_from_python_type_pycolor(mr, addr, cache).map(|value| value as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("Bunch"),
             |mr: &PythonMemoryReader, addr, cache| {
-                reading_from_python_type_bunch(mr, addr, cache).map(|value| value as Box<dyn Any>)
+                reading_from_python_type_bunch(mr, addr, cache).map(|value| value as Box<dyn Any + Send + Sync>)
             },
         );
         m.insert(
             String::from("Link"),
             |mr: &PythonMemoryReader, addr, cache| {
-                reading_from_python_type_link(mr, addr, cache).map(|value| value as Box<dyn Any>)
+                reading_from_python_type_link(mr, addr, cache).map(|value| value as Box<dyn Any + Send + Sync>)
             },
         );
         m
     };
+    static ref LONG_TEXT_KEYS: HashSet<&'static str> = ["htmlstr"].into_iter().collect();
     static ref DICT_ENTRIES_OF_INTEREST_KEYS: HashSet<&'static str> = {
         let mut set = HashSet::new();
         set.insert("_top");
@@ -289,6 +313,7 @@ lazy_static! {
         set.insert("_opacity");
         set.insert("_bgColor");
         set.insert("isExpanded");
+        set.insert("_checked");
         set
     };
 }
@@ -1,17 +1,34 @@
-﻿use crate::eve::interop::memory::models::memory_region::MemoryRegion;
+﻿use crate::eve::interop::memory::models::memory_read_stats::MemoryReadStats;
+use crate::eve::interop::memory::models::memory_region::MemoryRegion;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 #[cfg(windows)]
 use winapi::ctypes::c_void;
-use winapi::shared::minwindef::DWORD;
+use winapi::shared::minwindef::{DWORD, HMODULE};
 use winapi::um::handleapi::CloseHandle;
 use winapi::um::memoryapi::{ReadProcessMemory, VirtualQueryEx};
 use winapi::um::processthreadsapi::OpenProcess;
+use winapi::um::psapi::{EnumProcessModulesEx, GetModuleBaseNameW, LIST_MODULES_ALL};
 use winapi::um::winnt::{
     HANDLE, MEMORY_BASIC_INFORMATION, MEM_COMMIT, PAGE_GUARD, PAGE_NOACCESS,
     PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
 };
 
+const PAGE_SIZE: u64 = 0x1000;
+/// Larger reads (child lists, scan regions) go straight to `ReadProcessMemory`.
+const MAX_PAGE_CACHED_READ: u64 = 4 * PAGE_SIZE;
+
 pub struct WindowsMemoryReader {
     process_handle: HANDLE,
+    page_cache_enabled: AtomicBool,
+    /// Pages read in the current frame, `None` when the page could not be read.
+    pages: Mutex<HashMap<u64, Option<Arc<Vec<u8>>>>>,
+    requested_reads: AtomicU64,
+    syscalls: AtomicU64,
+    page_hits: AtomicU64,
+    page_misses: AtomicU64,
+    fallback_reads: AtomicU64,
 }
 
 impl WindowsMemoryReader {
@@ -22,7 +39,42 @@ impl WindowsMemoryReader {
         if process_handle.is_null() {
             None
         } else {
-            Some(WindowsMemoryReader { process_handle })
+            Some(WindowsMemoryReader {
+                process_handle,
+                page_cache_enabled: AtomicBool::new(false),
+                pages: Mutex::new(HashMap::new()),
+                requested_reads: AtomicU64::new(0),
+                syscalls: AtomicU64::new(0),
+                page_hits: AtomicU64::new(0),
+                page_misses: AtomicU64::new(0),
+                fallback_reads: AtomicU64::new(0),
+            })
+        }
+    }
+
+    /// Serves small reads from whole 4 KiB pages, read once per frame.
+    pub fn with_page_cache(self) -> Self {
+        self.page_cache_enabled.store(true, Ordering::Relaxed);
+        self
+    }
+
+    /// Drops the pages of the previous frame so the next reads see fresh memory.
+    pub(crate) fn begin_frame(&self) {
+        self.pages.lock().unwrap().clear();
+    }
+
+    /// Counters since the previous call, which resets them.
+    pub(crate) fn take_read_stats(&self) -> MemoryReadStats {
+        let requested_reads = self.requested_reads.swap(0, Ordering::Relaxed);
+        let syscalls = self.syscalls.swap(0, Ordering::Relaxed);
+
+        MemoryReadStats {
+            requested_reads,
+            syscalls,
+            page_hits: self.page_hits.swap(0, Ordering::Relaxed),
+            page_misses: self.page_misses.swap(0, Ordering::Relaxed),
+            fallback_reads: self.fallback_reads.swap(0, Ordering::Relaxed),
+            syscalls_saved: requested_reads as i64 - syscalls as i64,
         }
     }
 }
@@ -33,6 +85,56 @@ impl WindowsMemoryReader {
         start_address: u64,
         length: u64,
     ) -> Result<Vec<u8>, &'static str> {
+        self.requested_reads.fetch_add(1, Ordering::Relaxed);
+
+        if self.page_cache_enabled.load(Ordering::Relaxed) && length > 0 && length <= MAX_PAGE_CACHED_READ {
+            if let Some(bytes) = self.read_bytes_from_pages(start_address, length) {
+                return Ok(bytes);
+            }
+            self.fallback_reads.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.read_process_memory(start_address, length)
+    }
+
+    fn read_bytes_from_pages(&self, start_address: u64, length: u64) -> Option<Vec<u8>> {
+        let end_address = start_address.checked_add(length)?;
+        let mut buffer = Vec::with_capacity(length as usize);
+        let mut page_address = start_address & !(PAGE_SIZE - 1);
+
+        while page_address < end_address {
+            let page = self.read_page(page_address)?;
+            let from = (start_address.max(page_address) - page_address) as usize;
+            let to = (end_address.min(page_address + PAGE_SIZE) - page_address) as usize;
+            buffer.extend_from_slice(&page[from..to]);
+            page_address += PAGE_SIZE;
+        }
+
+        Some(buffer)
+    }
+
+    fn read_page(&self, page_address: u64) -> Option<Arc<Vec<u8>>> {
+        if let Some(page) = self.pages.lock().unwrap().get(&page_address) {
+            // A page known to be unreadable saves nothing, the caller falls back to a direct read.
+            if page.is_some() {
+                self.page_hits.fetch_add(1, Ordering::Relaxed);
+            }
+            return page.clone();
+        }
+
+        self.page_misses.fetch_add(1, Ordering::Relaxed);
+        let page = self.read_process_memory(page_address, PAGE_SIZE).ok().map(Arc::new);
+        self.pages.lock().unwrap().insert(page_address, page.clone());
+        page
+    }
+
+    fn read_process_memory(
+        &self,
+        start_address: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, &'static str> {
+        self.syscalls.fetch_add(1, Ordering::Relaxed);
+
         let mut buffer = vec![0u8; length as usize];
         let mut number_of_bytes_read: usize = 0;
 
@@ -90,6 +192,47 @@ impl WindowsMemoryReader {
 
         committed_regions
     }
+
+    /// Base address of the first loaded module whose file name matches `module_name`, ignoring case.
+    pub(crate) fn module_base_address(&self, module_name: &str) -> Option<u64> {
+        let mut modules: Vec<HMODULE> = vec![std::ptr::null_mut(); 1024];
+        let mut bytes_needed: DWORD = 0;
+
+        let success = unsafe {
+            EnumProcessModulesEx(
+                self.process_handle,
+                modules.as_mut_ptr(),
+                (modules.len() * size_of::<HMODULE>()) as DWORD,
+                &mut bytes_needed,
+                LIST_MODULES_ALL,
+            )
+        };
+
+        if success == 0 {
+            return None;
+        }
+
+        let module_count = (bytes_needed as usize / size_of::<HMODULE>()).min(modules.len());
+
+        modules[..module_count].iter().find_map(|&module| {
+            let mut name_buffer = [0u16; 260];
+            let name_length = unsafe {
+                GetModuleBaseNameW(
+                    self.process_handle,
+                    module,
+                    name_buffer.as_mut_ptr(),
+                    name_buffer.len() as DWORD,
+                )
+            };
+
+            let name = String::from_utf16_lossy(&name_buffer[..name_length as usize]);
+            if name.eq_ignore_ascii_case(module_name) {
+                Some(module as u64)
+            } else {
+                None
+            }
+        })
+    }
 }
 
 impl Drop for WindowsMemoryReader {
@@ -102,3 +245,98 @@ impl Drop for WindowsMemoryReader {
 
 unsafe impl Send for WindowsMemoryReader {}
 unsafe impl Sync for WindowsMemoryReader {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicU64};
+    use std::sync::{Arc, Mutex};
+    use crate::eve::interop::memory::windows_memory_reader::{WindowsMemoryReader, PAGE_SIZE};
+
+    /// Reader without a process whose page cache already holds `pages`, so every read that
+    /// is not served from them fails.
+    fn reader_with_pages(pages: Vec<(u64, Option<Vec<u8>>)>) -> WindowsMemoryReader {
+        WindowsMemoryReader {
+            process_handle: std::ptr::null_mut(),
+            page_cache_enabled: AtomicBool::new(true),
+            pages: Mutex::new(
+                pages
+                    .into_iter()
+                    .map(|(page_address, page)| (page_address, page.map(Arc::new)))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            requested_reads: AtomicU64::new(0),
+            syscalls: AtomicU64::new(0),
+            page_hits: AtomicU64::new(0),
+            page_misses: AtomicU64::new(0),
+            fallback_reads: AtomicU64::new(0),
+        }
+    }
+
+    fn page_filled_with(byte: u8) -> Vec<u8> {
+        vec![byte; PAGE_SIZE as usize]
+    }
+
+    #[test]
+    fn reads_within_one_page() {
+        let reader = reader_with_pages(vec![(0x10000, Some((0..PAGE_SIZE).map(|i| i as u8).collect()))]);
+
+        assert_eq!(reader.read_bytes(0x10010, 4).unwrap(), vec![0x10, 0x11, 0x12, 0x13]);
+
+        let stats = reader.take_read_stats();
+        assert_eq!((stats.requested_reads, stats.syscalls, stats.page_hits), (1, 0, 1));
+        assert_eq!(stats.syscalls_saved, 1);
+    }
+
+    #[test]
+    fn reads_across_page_boundaries() {
+        let reader = reader_with_pages(vec![
+            (0x10000, Some(page_filled_with(0xAA))),
+            (0x11000, Some(page_filled_with(0xBB))),
+            (0x12000, Some(page_filled_with(0xCC))),
+        ]);
+
+        let bytes = reader.read_bytes(0x10FFE, 4).unwrap();
+        assert_eq!(bytes, vec![0xAA, 0xAA, 0xBB, 0xBB]);
+
+        let bytes = reader.read_bytes(0x10FFF, PAGE_SIZE + 2).unwrap();
+        assert_eq!(bytes.len() as u64, PAGE_SIZE + 2);
+        assert_eq!(bytes[0], 0xAA);
+        assert!(bytes[1..=PAGE_SIZE as usize].iter().all(|byte| *byte == 0xBB));
+        assert_eq!(bytes[PAGE_SIZE as usize + 1], 0xCC);
+
+        let stats = reader.take_read_stats();
+        assert_eq!((stats.requested_reads, stats.syscalls, stats.page_hits), (2, 0, 5));
+    }
+
+    #[test]
+    fn unreadable_page_falls_back_to_a_direct_read() {
+        let reader = reader_with_pages(vec![
+            (0x10000, Some(page_filled_with(0xAA))),
+            (0x11000, None),
+        ]);
+
+        // The reader has no process, so the direct read fails as well.
+        assert!(reader.read_bytes(0x10FFE, 4).is_err());
+
+        let stats = reader.take_read_stats();
+        assert_eq!(stats.requested_reads, 1);
+        assert_eq!(stats.fallback_reads, 1);
+        assert_eq!(stats.syscalls, 1);
+        assert_eq!(stats.page_hits, 1);
+        assert_eq!(stats.syscalls_saved, 0);
+    }
+
+    #[test]
+    fn read_stats_are_reported_per_frame() {
+        let reader = reader_with_pages(vec![(0x10000, Some(page_filled_with(0xAA)))]);
+
+        reader.read_bytes(0x10000, 8).unwrap();
+        reader.read_bytes(0x10008, 8).unwrap();
+        assert_eq!(reader.take_read_stats().requested_reads, 2);
+
+        reader.read_bytes(0x10010, 8).unwrap();
+        assert_eq!(reader.take_read_stats().requested_reads, 1);
+        assert_eq!(reader.take_read_stats().requested_reads, 0);
+    }
+}
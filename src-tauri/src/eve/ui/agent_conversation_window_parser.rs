@@ -0,0 +1,130 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::agent_conversation_window::{AgentConversationButton, AgentConversationButtonAction, AgentConversationWindow};
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
+
+impl AgentConversationWindow {
+
+    pub fn parse_agent_conversation_windows(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Vec<Arc<AgentConversationWindow>> {
+        zones
+            .get(&UiZonesEnum::AgentConversation)
+            .map(|windows| {
+                windows
+                    .iter()
+                    .map(|node| Arc::new(AgentConversationWindow::parse(Arc::clone(node))))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> AgentConversationWindow {
+        let descendants =
+            DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
+
+        let agent_name = descendants
+            .iter()
+            .find(|child| child.node.ui_node.object_type_name == "WindowCaption")
+            .map(|child| UiTreeNode::get_display_text(&child.node.ui_node))
+            .and_then(|caption| AgentConversationWindow::agent_name_from_caption(&caption));
+
+        let html_sections = descendants
+            .iter()
+            .filter_map(|child| ParserUtils::get_string_property_from_dict_entries(&child.node.ui_node, "htmlstr"))
+            .flat_map(|html| HtmlParserUtils::parse_sections(&html))
+            .collect::<Vec<_>>();
+
+        let objectives_heading = t!("objectives").to_lowercase();
+        let rewards_heading = t!("rewards").to_lowercase();
+        let bonus_rewards_heading = t!("bonus_rewards").to_lowercase();
+
+        let mut mission_title = None;
+        let mut objectives = Vec::new();
+        let mut rewards = Vec::new();
+        let mut bonus_rewards = Vec::new();
+
+        for section in html_sections.iter() {
+            let heading = match &section.heading {
+                Some(heading) => heading,
+                None => continue,
+            };
+            let heading_lowercase = heading.to_lowercase();
+
+            // "Bonus Rewards" also contains "Rewards", so it has to be checked first.
+            if heading_lowercase.contains(&bonus_rewards_heading) {
+                bonus_rewards.extend(section.items.iter().cloned());
+            } else if heading_lowercase.contains(&rewards_heading) {
+                rewards.extend(section.items.iter().cloned());
+            } else if heading_lowercase.contains(&objectives_heading) {
+                objectives.extend(section.items.iter().cloned());
+            } else if mission_title.is_none() {
+                mission_title = Some(heading.clone());
+            }
+        }
+
+        let buttons = descendants
+            .iter()
+            .filter(|child| child.node.ui_node.object_type_name == "Button")
+            .filter_map(|child| {
+                let text = ParserUtils::get_all_contained_display_texts_with_region(&child.node)
+                    .into_iter()
+                    .map(|(text, _)| text)
+                    .next()?;
+
+                Some(AgentConversationButton {
+                    ui_node: Arc::clone(&child.node),
+                    action: AgentConversationWindow::button_action_from_text(&text),
+                    text,
+                })
+            })
+            .collect();
+
+        AgentConversationWindow {
+            ui_node: region_node,
+            agent_name,
+            mission_title,
+            objectives,
+            rewards,
+            bonus_rewards,
+            html_sections,
+            buttons,
+        }
+    }
+
+    fn agent_name_from_caption(caption: &str) -> Option<String> {
+        // The caption reads "Agent Conversation - <agent name>".
+        let agent_name = match caption.rsplit_once(" - ") {
+            Some((_, agent_name)) => agent_name,
+            None => caption,
+        };
+        let agent_name = HtmlParserUtils::strip_tags(agent_name);
+
+        if agent_name.is_empty() {
+            None
+        } else {
+            Some(agent_name)
+        }
+    }
+
+    /// Matches the whole button label against the localized labels, so "Accept Remotely"
+    /// style buttons or texts that merely contain a label are not taken for it.
+    fn button_action_from_text(text: &str) -> AgentConversationButtonAction {
+        let label = HtmlParserUtils::strip_tags(text).trim().to_lowercase();
+        let is_label = |key: &str| label == t!(key).to_lowercase();
+
+        if is_label("accept") {
+            AgentConversationButtonAction::Accept
+        } else if is_label("decline") {
+            AgentConversationButtonAction::Decline
+        } else if is_label("complete") {
+            AgentConversationButtonAction::Complete
+        } else if is_label("delay") {
+            AgentConversationButtonAction::Delay
+        } else {
+            AgentConversationButtonAction::Other
+        }
+    }
+}
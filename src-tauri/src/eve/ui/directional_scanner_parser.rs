@@ -1,30 +1,50 @@
 ﻿use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 use std::result;
+use log::warn;
 use regex::Regex;
 use crate::eve::ui::models::directional_scanner::{DirectionalScanner, DirectionalScannerEntry};
-use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui::parser_utils::{ParserUtils, KILOMETERS_PER_AU};
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
 use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
 use crate::eve::ui_tree_node::utils::utils::UiUtils;
 
+/// Position of the ship icon column in the default column order.
+const ICON_COLUMN_INDEX: usize = 3;
+
 impl DirectionalScanner {
     
-    pub fn parse_directional_scanner(zones: &HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>)-> Option<Rc<DirectionalScanner>>{
+    pub fn parse_directional_scanner(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>)-> Option<Arc<DirectionalScanner>>{
         let directional_scanner = zones.get(&UiZonesEnum::DirectionalScanner);
         if directional_scanner.is_none() {
             return None;
         }
         let directional_scanner = DirectionalScanner::parse(directional_scanner.unwrap()[0].clone());
         
-        Some(Rc::new(directional_scanner))
+        Some(Arc::new(directional_scanner))
     }
-    pub fn parse(region_node: Rc<UITreeNodeWithDisplayRegion>) -> DirectionalScanner {
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> DirectionalScanner {
         
         let childs_with_region =
             DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
 
+        let headers_container_node = childs_with_region.iter().find(|node| {
+            node.node
+                .ui_node
+                .object_type_name
+                .to_lowercase()
+                .contains("headers")
+        });
+
+        let entries_headers = headers_container_node
+            .map(|node| ParserUtils::get_all_contained_display_texts_with_region(&node.node))
+            .unwrap_or_default();
+
+        if entries_headers.is_empty() {
+            warn!("Directional scanner headers not found, falling back to the default column order");
+        }
+
         let list_node_with_entries = childs_with_region.iter().flat_map(|child| {
             if let Some(name_entry) = ParserUtils::get_name_from_dict_entries(&child.node.ui_node) {
                 if !name_entry.starts_with("entry_") {
@@ -34,57 +54,95 @@ impl DirectionalScanner {
                 return None;
             }
 
-            return DirectionalScanner::extract_entry(&child.node);
+            return DirectionalScanner::extract_entry(&entries_headers, &child.node);
             
         }).collect::<Vec<DirectionalScannerEntry>>();
 
+        let mut range_input = None;
+        let mut angle_selector = None;
+        let mut use_overview_settings_toggle = None;
+        let mut scan_button = None;
+
+        for control in childs_with_region.iter().filter_map(|child| ParserUtils::parse_control(&child.node)) {
+            let name = control.name.to_lowercase();
+            if name.contains("angle") {
+                angle_selector.get_or_insert(control);
+            } else if name.contains("overview") || name.contains("preset") {
+                use_overview_settings_toggle.get_or_insert(control);
+            } else if name.contains("range") || name.contains("distance") {
+                range_input.get_or_insert(control);
+            } else if name.contains("scan") {
+                scan_button.get_or_insert(control);
+            }
+        }
+
+        let range_in_km = range_input
+            .as_ref()
+            .and_then(|control| control.text.as_ref())
+            .and_then(|text| DirectionalScanner::parse_range_in_km(text));
+
+        let angle_in_degrees = angle_selector
+            .as_ref()
+            .and_then(|control| control.text.as_ref())
+            .and_then(|text| ParserUtils::parse_quantity(text))
+            .map(|angle| angle as i32);
+
+        let use_overview_settings = use_overview_settings_toggle
+            .as_ref()
+            .and_then(|control| control.is_checked);
+
         DirectionalScanner {
             ui_node: region_node,
             entries: list_node_with_entries,
+            range_input,
+            range_in_km,
+            angle_selector,
+            angle_in_degrees,
+            use_overview_settings_toggle,
+            use_overview_settings,
+            scan_button,
+        }
+    }
+
+    fn parse_range_in_km(range_text: &str) -> Option<f64> {
+        // The range field holds plain kilometers unless the client shows a unit.
+        match ParserUtils::parse_number_with_unit(range_text) {
+            Some((value, unit)) if unit == "au" => Some(value * KILOMETERS_PER_AU),
+            Some((value, unit)) if unit == "km" => Some(value),
+            Some((value, unit)) if unit == "m" => Some(value / 1000.0),
+            Some(_) => None,
+            None => ParserUtils::parse_decimal_number(range_text),
         }
     }
     
-    fn extract_entry(node: &Rc<UITreeNodeWithDisplayRegion>)-> Option<DirectionalScannerEntry>{
-        
-        let ship_icon_node = node.child_with_region.get(3)
-            .and_then(|child1| child1.node.child_with_region.get(0))
-            .map(|child2| &child2.node.ui_node);
-        
-        let ship_icon = if let Some(ship_icon_node) = ship_icon_node {
-            let result = ParserUtils::get_string_property_from_dict_entries(ship_icon_node, 
-                                                    "_texturePath");
-            result
-        }else { None };
-        
-        let ship_name_node = node.child_with_region.get(2)
-            .and_then(|child1| child1.node.child_with_region.get(0))
-            .map(|child2| &child2.node.ui_node);
-        
-        let ship_name = if let Some(ship_name_node) = ship_name_node {
-            UiTreeNode::get_display_text(ship_name_node)
-        }else { "".parse().unwrap() };
-        
-        let ship_type_node = node.child_with_region.get(1)
-            .and_then(|child1| child1.node.child_with_region.get(0))
-            .map(|child2| &child2.node.ui_node);
-        
-        let ship_type = if let Some(ship_type_node) = ship_type_node {
-            UiTreeNode::get_display_text(ship_type_node)
-        }else { "".parse().unwrap() };
-        
+    fn extract_entry(
+        entries_headers: &Vec<(String, Arc<UITreeNodeWithDisplayRegion>)>,
+        node: &Arc<UITreeNodeWithDisplayRegion>,
+    ) -> Option<DirectionalScannerEntry> {
+
+        let cells_texts = if entries_headers.is_empty() {
+            DirectionalScanner::extract_cells_texts_by_position(node)
+        } else {
+            ParserUtils::parse_list_view_entry(entries_headers, Arc::clone(node))
+        };
+
+        let ship_icon = DirectionalScanner::extract_ship_icon(entries_headers, node);
+
+        let ship_name = cells_texts
+            .get(t!("name").as_ref())
+            .cloned()
+            .unwrap_or_default();
+
+        let ship_type = cells_texts
+            .get(t!("type").as_ref())
+            .cloned()
+            .unwrap_or_default();
+
         let ship_type_localized = ParserUtils::extract_localized_name(&ship_type).unwrap_or("empty_default".parse().unwrap());
-        
-        let distance_node = node.child_with_region.get(0)
-            .and_then(|child1| child1.node.child_with_region.get(0))
-            .map(|child2| &child2.node.ui_node);
-        
-        
-        let distance = if let Some(distance_node) = distance_node {
-            let distance_string = UiTreeNode::get_display_text(distance_node);
 
-            let distance = ParserUtils::parse_distance_in_meters_from_text(&Some(distance_string));
-            distance
-        }else { None };
+        let distance = ParserUtils::parse_distance_in_meters_from_text(
+            &cells_texts.get(t!("distance").as_ref()).cloned(),
+        );
         
         if  ship_icon.is_some()  {
             return  Some(DirectionalScannerEntry {
@@ -92,8 +150,52 @@ impl DirectionalScanner {
                 names: ship_name,
                 ship_type: ship_type_localized,
                 ship_icon: ship_icon.unwrap(),
+                cells_texts,
             });
         } 
         None
     }
+
+    fn extract_ship_icon(
+        entries_headers: &[(String, Arc<UITreeNodeWithDisplayRegion>)],
+        node: &Arc<UITreeNodeWithDisplayRegion>,
+    ) -> Option<String> {
+        // The icon column has no header text, so it is the column no header lines up with.
+        let header_less_column = || {
+            node.child_with_region.iter().find(|column| {
+                !entries_headers.iter().any(|(_, header)| {
+                    ParserUtils::header_region_matches_cell_region(
+                        &header.total_display_region,
+                        &column.node.total_display_region,
+                    )
+                })
+            })
+        };
+
+        let icon_column = if entries_headers.is_empty() {
+            node.child_with_region.get(ICON_COLUMN_INDEX)
+        } else {
+            header_less_column().or_else(|| node.child_with_region.get(ICON_COLUMN_INDEX))
+        }?;
+
+        let icon_node = icon_column.node.child_with_region.first()?;
+
+        ParserUtils::get_string_property_from_dict_entries(&icon_node.node.ui_node, "_texturePath")
+    }
+
+    fn extract_cells_texts_by_position(node: &Arc<UITreeNodeWithDisplayRegion>) -> HashMap<String, String> {
+        // Default column order of the scanner results when the headers are not in the tree.
+        let columns = [t!("distance"), t!("type"), t!("name")];
+
+        columns
+            .iter()
+            .enumerate()
+            .filter_map(|(column_index, header)| {
+                let cell_node = node.child_with_region.get(column_index)
+                    .and_then(|column| column.node.child_with_region.first())?;
+
+                Some((header.to_string(), UiTreeNode::get_display_text(&cell_node.node.ui_node)))
+            })
+            .collect()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+﻿use lazy_static::lazy_static;
+use regex::Regex;
+use crate::eve::ui::models::agent_conversation_window::HtmlSection;
+
+pub struct HtmlParserUtils {}
+
+impl HtmlParserUtils {
+    /*
+    The client renders agent and mission texts from a small HTML subset. Sections start with a
+    subheader span (or a plain heading) and their items are either table rows or lines split by <br>:
+    <span id=subheader>Objectives</span><br>The following objectives must be completed...<table><tr><td>...
+    */
+    pub fn parse_sections(html: &str) -> Vec<HtmlSection> {
+        let mut sections = Vec::new();
+        let mut heading: Option<String> = None;
+        let mut body_start = 0;
+
+        for captures in HEADING_REGEX.captures_iter(html) {
+            let whole_match = captures.get(0).unwrap();
+
+            HtmlParserUtils::push_section(&mut sections, heading.take(), &html[body_start..whole_match.start()]);
+
+            heading = captures
+                .get(1)
+                .or_else(|| captures.get(2))
+                .map(|heading_match| HtmlParserUtils::strip_tags(heading_match.as_str()))
+                .filter(|text| !text.is_empty());
+            body_start = whole_match.end();
+        }
+
+        HtmlParserUtils::push_section(&mut sections, heading, &html[body_start..]);
+
+        sections
+    }
+
+    pub fn strip_tags(html: &str) -> String {
+        let text = TAG_REGEX.replace_all(html, " ");
+
+        let decoded = text
+            .replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&");
+
+        decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn push_section(sections: &mut Vec<HtmlSection>, heading: Option<String>, body: &str) {
+        let items = HtmlParserUtils::parse_items(body);
+
+        if heading.is_none() && items.is_empty() {
+            return;
+        }
+
+        sections.push(HtmlSection { heading, items });
+    }
+
+    fn parse_items(body: &str) -> Vec<String> {
+        let rows: Vec<String> = ROW_REGEX
+            .captures_iter(body)
+            .map(|row| {
+                CELL_REGEX
+                    .captures_iter(&row[1])
+                    .map(|cell| HtmlParserUtils::strip_tags(&cell[1]))
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        if !rows.is_empty() {
+            return rows;
+        }
+
+        LINE_BREAK_REGEX
+            .split(body)
+            .map(HtmlParserUtils::strip_tags)
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+}
+
+lazy_static! {
+    static ref HEADING_REGEX: Regex =
+        Regex::new(r#"(?is)<span[^>]*id\s*=\s*"?subheader"?[^>]*>(.*?)</span>|<h[1-6][^>]*>(.*?)</h[1-6]>"#).unwrap();
+    static ref ROW_REGEX: Regex = Regex::new(r"(?is)<tr[^>]*>(.*?)</tr>").unwrap();
+    static ref CELL_REGEX: Regex = Regex::new(r"(?is)<td[^>]*>(.*?)</td>").unwrap();
+    static ref LINE_BREAK_REGEX: Regex = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    static ref TAG_REGEX: Regex = Regex::new(r"(?s)<[^>]*>").unwrap();
+}
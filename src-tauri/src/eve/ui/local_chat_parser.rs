@@ -0,0 +1,45 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::local_chat::{LocalChat, LocalChatMember};
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
+
+const LOCAL_CHANNEL_NAME_PREFIX: &str = "chatchannel_local";
+
+impl LocalChat {
+
+    pub fn parse_local_chat(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Option<Arc<LocalChat>> {
+        let local_chat = zones.get(&UiZonesEnum::ChatWindow)?.iter().find(|node| {
+            ParserUtils::get_name_from_dict_entries(&node.ui_node)
+                .is_some_and(|name| name.to_lowercase().starts_with(LOCAL_CHANNEL_NAME_PREFIX))
+        })?;
+
+        Some(Arc::new(LocalChat::parse(Arc::clone(local_chat))))
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> LocalChat {
+        let members = DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region)
+            .iter()
+            .filter(|child| child.node.ui_node.object_type_name.contains("UserEntry"))
+            .filter_map(|child| {
+                let name = ParserUtils::get_all_contained_display_texts_with_region(&child.node)
+                    .into_iter()
+                    .map(|(text, _)| HtmlParserUtils::strip_tags(&text))
+                    .find(|text| !text.is_empty())?;
+
+                Some(LocalChatMember {
+                    ui_node: Arc::clone(&child.node),
+                    name,
+                })
+            })
+            .collect();
+
+        LocalChat {
+            ui_node: region_node,
+            members,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::location_info::LocationInfo;
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+
+lazy_static! {
+    // The header links the system with "showinfo:5//<solarSystemID>", followed by its security status.
+    static ref SYSTEM_LINK_REGEX: Regex = Regex::new(r"<url=showinfo:5//\d+[^>]*>(.*?)</url>").unwrap();
+    static ref SECURITY_STATUS_REGEX: Regex = Regex::new(r"(-?\d+[.,]\d)").unwrap();
+}
+
+impl LocationInfo {
+
+    pub fn parse_location_info(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Option<Arc<LocationInfo>> {
+        let location_info = zones.get(&UiZonesEnum::LocationInfo)?.first()?;
+
+        Some(Arc::new(LocationInfo::parse(Arc::clone(location_info))))
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> LocationInfo {
+        let texts = ParserUtils::get_all_contained_display_texts_with_region(&region_node)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>();
+
+        let header = texts.iter().find(|text| SYSTEM_LINK_REGEX.is_match(text));
+
+        let solar_system_name = header
+            .and_then(|text| SYSTEM_LINK_REGEX.captures(text))
+            .map(|captures| HtmlParserUtils::strip_tags(&captures[1]))
+            .filter(|name| !name.is_empty());
+
+        let security_status = header
+            .map(|text| SYSTEM_LINK_REGEX.replace(text, ""))
+            .and_then(|rest| {
+                SECURITY_STATUS_REGEX
+                    .captures(&HtmlParserUtils::strip_tags(&rest))
+                    .and_then(|captures| ParserUtils::parse_decimal_number(&captures[1]))
+            });
+
+        LocationInfo {
+            ui_node: region_node,
+            solar_system_name,
+            security_status,
+        }
+    }
+}
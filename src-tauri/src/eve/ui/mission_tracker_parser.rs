@@ -0,0 +1,188 @@
+﻿use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::mission_tracker::{MissionObjective, MissionTrackerPanel, TrackedMission};
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
+
+const OBJECTIVE_COMPLETED_TEXTURE_HINTS: [&str; 2] = ["check", "complete"];
+/// Mission entries in the info panels are their own node types, their first text is the title.
+const MISSION_TITLE_TYPE_HINTS: [&str; 3] = ["MissionEntry", "MissionHeader", "MissionTitle"];
+const MISSION_TITLE_NAME_HINTS: [&str; 2] = ["missionname", "missiontitle"];
+
+impl MissionTrackerPanel {
+
+    pub fn parse_mission_tracker_panels(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Vec<Arc<MissionTrackerPanel>> {
+        zones
+            .get(&UiZonesEnum::MissionTracker)
+            .map(|panels| {
+                panels
+                    .iter()
+                    .map(|node| Arc::new(MissionTrackerPanel::parse(Arc::clone(node))))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> MissionTrackerPanel {
+        let descendants =
+            DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
+
+        let objective_nodes = descendants
+            .iter()
+            .filter(|child| child.node.ui_node.object_type_name.contains("Objective"))
+            .map(|child| Arc::clone(&child.node))
+            .collect::<Vec<_>>();
+
+        // Objective rows can wrap their own objective labels, only the outermost node is an objective.
+        let nested_objective_addresses: HashSet<u64> = objective_nodes
+            .iter()
+            .flat_map(|node| DisplayRegionUtils::list_descendants_with_display_region(&node.child_with_region))
+            .map(|child| child.node.ui_node.object_address)
+            .collect();
+
+        let objectives = objective_nodes
+            .into_iter()
+            .filter(|node| !nested_objective_addresses.contains(&node.ui_node.object_address))
+            .filter_map(MissionTrackerPanel::parse_objective)
+            .collect::<Vec<_>>();
+
+        let title_nodes = descendants
+            .iter()
+            .filter(|child| MissionTrackerPanel::is_mission_title_node(&child.node.ui_node))
+            .collect::<Vec<_>>();
+
+        // A mission entry can hold its own title node, only the outermost one is kept.
+        let nested_title_addresses: HashSet<u64> = title_nodes
+            .iter()
+            .flat_map(|child| DisplayRegionUtils::list_descendants_with_display_region(&child.node.child_with_region))
+            .map(|child| child.node.ui_node.object_address)
+            .collect();
+
+        let mut mission_titles = title_nodes
+            .into_iter()
+            .filter(|child| !nested_title_addresses.contains(&child.node.ui_node.object_address))
+            .filter_map(|child| {
+                let text = std::iter::once(UiTreeNode::get_display_text(&child.node.ui_node))
+                    .chain(
+                        ParserUtils::get_all_contained_display_texts_with_region(&child.node)
+                            .into_iter()
+                            .map(|(text, _)| text),
+                    )
+                    .map(|text| HtmlParserUtils::strip_tags(&text))
+                    .find(|text| !text.is_empty())?;
+
+                Some((text, child.node.total_display_region.y))
+            })
+            .collect::<Vec<_>>();
+
+        mission_titles.sort_by_key(|(_, y)| *y);
+
+        MissionTrackerPanel {
+            panel_type: region_node.ui_node.object_type_name.clone(),
+            ui_node: region_node,
+            missions: MissionTrackerPanel::group_objectives_by_title(mission_titles, objectives),
+        }
+    }
+
+    fn is_mission_title_node(ui_node: &UiTreeNode) -> bool {
+        let type_name = &ui_node.object_type_name;
+        if type_name.contains("Objective") {
+            return false;
+        }
+
+        let name = ParserUtils::get_name_from_dict_entries(ui_node)
+            .unwrap_or_default()
+            .to_lowercase();
+
+        MISSION_TITLE_TYPE_HINTS.iter().any(|hint| type_name.contains(hint))
+            || MISSION_TITLE_NAME_HINTS.iter().any(|hint| name.contains(hint))
+    }
+
+    fn parse_objective(objective_node: Arc<UITreeNodeWithDisplayRegion>) -> Option<MissionObjective> {
+        let mut texts = vec![UiTreeNode::get_display_text(&objective_node.ui_node)];
+        texts.extend(
+            ParserUtils::get_all_contained_display_texts_with_region(&objective_node)
+                .into_iter()
+                .map(|(text, _)| text),
+        );
+
+        let text = texts
+            .iter()
+            .map(|text| HtmlParserUtils::strip_tags(text))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let is_completed = std::iter::once(&objective_node.ui_node)
+            .chain(
+                DisplayRegionUtils::list_descendants_with_display_region(&objective_node.child_with_region)
+                    .iter()
+                    .map(|child| &child.node.ui_node),
+            )
+            .filter_map(|ui_node| ParserUtils::get_string_property_from_dict_entries(ui_node, "_texturePath"))
+            .any(|texture_path| {
+                let texture_path = texture_path.to_lowercase();
+                OBJECTIVE_COMPLETED_TEXTURE_HINTS
+                    .iter()
+                    .any(|hint| texture_path.contains(hint))
+            });
+
+        Some(MissionObjective {
+            ui_node: objective_node,
+            text,
+            is_completed,
+        })
+    }
+
+    fn group_objectives_by_title(
+        mission_titles: Vec<(String, i32)>,
+        objectives: Vec<MissionObjective>,
+    ) -> Vec<TrackedMission> {
+        let mut untitled_objectives = Vec::new();
+        let mut objectives_by_title: Vec<Vec<MissionObjective>> =
+            mission_titles.iter().map(|_| Vec::new()).collect();
+
+        // Every objective belongs to the closest title above it.
+        for objective in objectives {
+            let objective_y = objective.ui_node.total_display_region.y;
+
+            match mission_titles.iter().rposition(|(_, title_y)| *title_y <= objective_y) {
+                Some(index) => objectives_by_title[index].push(objective),
+                None => untitled_objectives.push(objective),
+            }
+        }
+
+        let mut missions = Vec::new();
+
+        if !untitled_objectives.is_empty() {
+            missions.push(MissionTrackerPanel::tracked_mission(None, untitled_objectives));
+        }
+
+        missions.extend(
+            mission_titles
+                .into_iter()
+                .zip(objectives_by_title)
+                .map(|((title, _), objectives)| MissionTrackerPanel::tracked_mission(Some(title), objectives)),
+        );
+
+        missions
+    }
+
+    fn tracked_mission(title: Option<String>, objectives: Vec<MissionObjective>) -> TrackedMission {
+        let is_completed =
+            !objectives.is_empty() && objectives.iter().all(|objective| objective.is_completed);
+
+        TrackedMission {
+            title,
+            objectives,
+            is_completed,
+        }
+    }
+}
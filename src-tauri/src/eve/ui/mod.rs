@@ -1,5 +1,13 @@
 ﻿pub mod models;
 pub mod overview_window_parser;
 pub mod parser_utils;
+pub mod html_parser_utils;
 mod directional_scanner_parser;
-mod probe_scanner_parser;
\ No newline at end of file
+mod probe_scanner_parser;
+mod regional_market_parser;
+mod agent_conversation_window_parser;
+mod mission_tracker_parser;
+mod module_button_tooltip_parser;
+mod planetary_colony_parser;
+mod location_info_parser;
+mod local_chat_parser;
\ No newline at end of file
@@ -0,0 +1,39 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct AgentConversationWindow {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub agent_name: Option<String>,
+    pub mission_title: Option<String>,
+    pub objectives: Vec<String>,
+    pub rewards: Vec<String>,
+    pub bonus_rewards: Vec<String>,
+    pub html_sections: Vec<HtmlSection>,
+    pub buttons: Vec<AgentConversationButton>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HtmlSection {
+    pub heading: Option<String>,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum AgentConversationButtonAction {
+    Accept,
+    Decline,
+    Complete,
+    Delay,
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgentConversationButton {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub text: String,
+    pub action: AgentConversationButtonAction,
+}
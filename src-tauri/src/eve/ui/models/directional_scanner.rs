@@ -1,12 +1,21 @@
-﻿use std::rc::Rc;
+﻿use std::collections::HashMap;
+use std::sync::Arc;
 use serde::Serialize;
+use crate::eve::ui::models::ui_control::UiControl;
 use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
 
 #[derive(Debug, Serialize)]
 pub struct DirectionalScanner {
     #[serde(skip_serializing)]
-    pub ui_node: Rc<UITreeNodeWithDisplayRegion>,
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
     pub entries: Vec<DirectionalScannerEntry>,
+    pub range_input: Option<UiControl>,
+    pub range_in_km: Option<f64>,
+    pub angle_selector: Option<UiControl>,
+    pub angle_in_degrees: Option<i32>,
+    pub use_overview_settings_toggle: Option<UiControl>,
+    pub use_overview_settings: Option<bool>,
+    pub scan_button: Option<UiControl>,
 }
 
 #[derive(Debug, Serialize)]
@@ -15,4 +24,5 @@ pub struct DirectionalScannerEntry {
     pub names: String,
     pub ship_type: String,
     pub ship_icon: String,
+    pub cells_texts: HashMap<String, String>,
 }
\ No newline at end of file
@@ -1,26 +1,92 @@
 ﻿use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 use serde::Serialize;
+use crate::eve::ui::models::agent_conversation_window::AgentConversationWindow;
 use crate::eve::ui::models::directional_scanner::DirectionalScanner;
-use crate::eve::ui::models::overview_window::OverviewWindow;
+use crate::eve::ui::models::local_chat::LocalChat;
+use crate::eve::ui::models::location_info::LocationInfo;
+use crate::eve::ui::models::mission_tracker::MissionTrackerPanel;
+use crate::eve::ui::models::module_button_tooltip::ModuleButtonTooltip;
+use crate::eve::ui::models::overview_window::{OverviewWindow, OverviewWindowEntry};
+use crate::eve::ui::models::planetary_colony::PlanetaryColony;
 use crate::eve::ui::models::probe_scanner::ProbeScanner;
+use crate::eve::ui::models::regional_market::RegionalMarket;
 use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
 use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct GeneralWindow {
-    pub overview_windows: Vec<Rc<OverviewWindow>>,
-    pub directional_scanner: Option<Rc<DirectionalScanner>>,
-    pub probe_scanner: Option<Rc<ProbeScanner>>
-    
+    pub overview_windows: Vec<Arc<OverviewWindow>>,
+    pub directional_scanner: Option<Arc<DirectionalScanner>>,
+    pub probe_scanner: Option<Arc<ProbeScanner>>,
+    pub regional_market: Option<Arc<RegionalMarket>>,
+    pub agent_conversation_windows: Vec<Arc<AgentConversationWindow>>,
+    pub mission_tracker_panels: Vec<Arc<MissionTrackerPanel>>,
+    pub module_button_tooltip: Option<Arc<ModuleButtonTooltip>>,
+    pub planetary_colony: Option<Arc<PlanetaryColony>>,
+    pub location_info: Option<Arc<LocationInfo>>,
+    pub local_chat: Option<Arc<LocalChat>>,
+
 }
 
 impl GeneralWindow {
-    pub fn parse_general_window(defined_zones: HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>) -> GeneralWindow {
+    pub fn parse_general_window(defined_zones: HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> GeneralWindow {
         GeneralWindow {
             overview_windows: OverviewWindow::parse_overview_windows(&defined_zones),
             directional_scanner: DirectionalScanner::parse_directional_scanner(&defined_zones),
-            probe_scanner: ProbeScanner::parse_probe_scanner(&defined_zones)
+            probe_scanner: ProbeScanner::parse_probe_scanner(&defined_zones),
+            regional_market: RegionalMarket::parse_regional_market(&defined_zones),
+            agent_conversation_windows: AgentConversationWindow::parse_agent_conversation_windows(&defined_zones),
+            mission_tracker_panels: MissionTrackerPanel::parse_mission_tracker_panels(&defined_zones),
+            module_button_tooltip: ModuleButtonTooltip::parse_module_button_tooltip(&defined_zones),
+            planetary_colony: PlanetaryColony::parse_planetary_colony(&defined_zones),
+            location_info: LocationInfo::parse_location_info(&defined_zones),
+            local_chat: LocalChat::parse_local_chat(&defined_zones),
+        }
+    }
+
+    /// Overview entries of a frame that has not been shared yet, so the trackers can fill in
+    /// their per-frame fields. Windows already shared elsewhere are skipped.
+    pub fn overview_entries_mut(&mut self) -> impl Iterator<Item = &mut OverviewWindowEntry> {
+        self.overview_windows
+            .iter_mut()
+            .filter_map(Arc::get_mut)
+            .flat_map(|overview| overview.entries.iter_mut())
+    }
+
+    pub fn current_solar_system_name(&self) -> Option<String> {
+        self.location_info
+            .as_ref()
+            .and_then(|location_info| location_info.solar_system_name.clone())
+    }
+
+    pub fn has_expired_extractors(&self) -> bool {
+        self.planetary_colony
+            .as_ref()
+            .is_some_and(|colony| colony.has_expired_extractors)
+    }
+}
+
+#[cfg(test)]
+impl GeneralWindow {
+    /// Frame holding a single overview window and nothing else.
+    pub fn with_overview_entries(entries: Vec<OverviewWindowEntry>) -> GeneralWindow {
+        GeneralWindow {
+            overview_windows: vec![Arc::new(OverviewWindow {
+                ui_node: UITreeNodeWithDisplayRegion::empty(),
+                entries_headers: Vec::new(),
+                entries,
+                scroll_controls: None,
+            })],
+            directional_scanner: None,
+            probe_scanner: None,
+            regional_market: None,
+            agent_conversation_windows: Vec::new(),
+            mission_tracker_panels: Vec::new(),
+            module_button_tooltip: None,
+            planetary_colony: None,
+            location_info: None,
+            local_chat: None,
         }
     }
-}
\ No newline at end of file
+}
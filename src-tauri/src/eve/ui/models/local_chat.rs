@@ -0,0 +1,17 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct LocalChat {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub members: Vec<LocalChatMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalChatMember {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub name: String,
+}
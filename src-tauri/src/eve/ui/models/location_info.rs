@@ -0,0 +1,11 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct LocationInfo {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub solar_system_name: Option<String>,
+    pub security_status: Option<f64>,
+}
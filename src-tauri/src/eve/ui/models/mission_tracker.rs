@@ -0,0 +1,26 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct MissionTrackerPanel {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub panel_type: String,
+    pub missions: Vec<TrackedMission>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrackedMission {
+    pub title: Option<String>,
+    pub objectives: Vec<MissionObjective>,
+    pub is_completed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissionObjective {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub text: String,
+    pub is_completed: bool,
+}
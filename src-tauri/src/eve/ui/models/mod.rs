@@ -2,3 +2,11 @@
 pub mod general_window;
 pub mod directional_scanner;
 pub mod probe_scanner;
+pub mod regional_market;
+pub mod agent_conversation_window;
+pub mod mission_tracker;
+pub mod module_button_tooltip;
+pub mod planetary_colony;
+pub mod ui_control;
+pub mod location_info;
+pub mod local_chat;
@@ -0,0 +1,19 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct ModuleButtonTooltip {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    #[serde(skip_serializing)]
+    pub module_button: Option<Arc<UITreeNodeWithDisplayRegion>>,
+    pub module_button_region: Option<DisplayRegion>,
+    pub rows: Vec<String>,
+    pub shortcut: Option<String>,
+    pub optimal_range_in_meters: Option<i32>,
+    pub falloff_in_meters: Option<i32>,
+    pub cycle_time_in_seconds: Option<f64>,
+    pub capacitor_use_in_gj: Option<f64>,
+}
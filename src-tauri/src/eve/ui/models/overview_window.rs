@@ -1,5 +1,5 @@
 ﻿use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::sync::Arc;
 use serde::Serialize;
 use crate::eve::ui_tree_node::common::common::ColorComponents;
 use crate::eve::ui_tree_node::models::ui_tree_node::{ScrollControls, UITreeNodeWithDisplayRegion};
@@ -8,9 +8,9 @@ use crate::eve::ui_tree_node::ui_constants::{UiZonesEnum, UI_ZONES};
 #[derive(Debug, Serialize)]
 pub struct OverviewWindow {
     #[serde(skip_serializing)]
-    pub ui_node: Rc<UITreeNodeWithDisplayRegion>,
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
     #[serde(skip_serializing)]
-    pub entries_headers: Vec<(String, Rc<UITreeNodeWithDisplayRegion>)>,
+    pub entries_headers: Vec<(String, Arc<UITreeNodeWithDisplayRegion>)>,
     pub entries: Vec<OverviewWindowEntry>,
     #[serde(skip_serializing)]
     pub scroll_controls: Option<ScrollControls>,
@@ -18,27 +18,32 @@ pub struct OverviewWindow {
 
 impl OverviewWindow {
     pub fn parse_overview_windows(
-        zones: &HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>,
-    ) -> Vec<Rc<OverviewWindow>> {
+        zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>,
+    ) -> Vec<Arc<OverviewWindow>> {
         let overview_windows = zones.get(&UiZonesEnum::Overview).unwrap();
         let overview_windows_with_region = overview_windows
             .iter()
-            .map(|node| OverviewWindow::parse_overview_window(Rc::clone(node)))
-            .collect::<Vec<Rc<OverviewWindow>>>();
+            .map(|node| OverviewWindow::parse_overview_window(Arc::clone(node)))
+            .collect::<Vec<Arc<OverviewWindow>>>();
         
         overview_windows_with_region
         
     }
 
-    fn parse_overview_window(region_node: Rc<UITreeNodeWithDisplayRegion>) -> Rc<OverviewWindow> {
-        Rc::new(OverviewWindow::parse(region_node))
+    fn parse_overview_window(region_node: Arc<UITreeNodeWithDisplayRegion>) -> Arc<OverviewWindow> {
+        Arc::new(OverviewWindow::parse(region_node))
     }
 }
 
 #[derive(Debug, Serialize)]
 pub struct OverviewWindowEntry {
     #[serde(skip_serializing)]
-    pub ui_node: Rc<UITreeNodeWithDisplayRegion>,
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    /// Stable across frames once assigned by `OverviewEntityTracker`.
+    pub entity_id: Option<u64>,
+    pub object_address: u64,
+    pub closing_speed_in_meters_per_second: Option<f64>,
+    pub time_to_contact_in_seconds: Option<f64>,
     pub texts_left_to_right: Vec<String>,
     pub cells_texts: HashMap<String, String>,
     pub object_distance: Option<String>,
@@ -61,4 +66,37 @@ pub struct OverviewWindowEntryCommonIndications {
     pub targeted_by_me: bool,
     pub is_jamming_me: bool,
     pub is_warp_disrupting_me: bool,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+impl OverviewWindowEntry {
+    /// Overview row as parsed from a plain entry, for tests of the frame trackers and alerts.
+    pub fn for_test(object_address: u64, name: &str, distance_in_meters: i32, is_player: bool) -> OverviewWindowEntry {
+        OverviewWindowEntry {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            entity_id: None,
+            object_address,
+            closing_speed_in_meters_per_second: None,
+            time_to_contact_in_seconds: None,
+            texts_left_to_right: Vec::new(),
+            cells_texts: HashMap::new(),
+            object_distance: Some(format!("{} m", distance_in_meters)),
+            object_distance_in_meters: Some(distance_in_meters),
+            object_name: Some(name.to_string()),
+            object_type: Some("Sabre".to_string()),
+            object_alliance: None,
+            is_player,
+            icon_sprite_color_percent: None,
+            names_under_space_object_icon: HashSet::new(),
+            bg_color_fills_percent: Vec::new(),
+            right_aligned_icons_hints: Vec::new(),
+            common_indications: OverviewWindowEntryCommonIndications {
+                targeting: false,
+                targeted_by_me: false,
+                is_jamming_me: false,
+                is_warp_disrupting_me: false,
+            },
+            opacity_percent: None,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct PlanetaryColony {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub planet_name: Option<String>,
+    pub extractors: Vec<PlanetExtractor>,
+    pub storage_facilities: Vec<PlanetStorageFacility>,
+    pub launchpad: Option<PlanetStorageFacility>,
+    pub has_expired_extractors: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanetExtractor {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub rows: Vec<String>,
+    pub head_count: Option<usize>,
+    pub remaining_cycle_time_in_seconds: Option<i64>,
+    pub is_expired: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanetStorageFacility {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub name: Option<String>,
+    pub contents: Vec<PlanetStorageContent>,
+    pub used_capacity_in_m3: Option<f64>,
+    pub capacity_in_m3: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanetStorageContent {
+    pub name: String,
+    pub quantity: Option<i64>,
+}
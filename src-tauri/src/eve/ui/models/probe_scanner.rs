@@ -1,12 +1,17 @@
-﻿use std::rc::Rc;
+﻿use std::sync::Arc;
 use serde::Serialize;
+use crate::eve::ui::models::ui_control::UiControl;
 use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
 
 #[derive(Debug, Serialize)]
 pub struct ProbeScanner {
     #[serde(skip_serializing)]
-    pub ui_node: Rc<UITreeNodeWithDisplayRegion>,
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
     pub entries: Vec<ProbeScannerEntry>,
+    pub probes: Vec<ProbeScannerProbe>,
+    pub scan_button: Option<UiControl>,
+    pub formation_controls: Vec<UiControl>,
+    pub range_controls: Vec<UiControl>,
 }
 
 #[derive(Debug, Serialize)]
@@ -16,5 +21,16 @@ pub struct ProbeScannerEntry {
     pub id: String,
     pub name: String,
     pub signal_strength: String,
+    pub signal_strength_percent: Option<f64>,
     pub type_emplacement: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProbeScannerProbe {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub name: String,
+    pub range: Option<String>,
+    pub range_in_au: Option<f64>,
+    pub expiry_in_seconds: Option<i64>,
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct RegionalMarket {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub selected_type_name: Option<String>,
+    pub sell_orders: Vec<MarketOrderEntry>,
+    pub buy_orders: Vec<MarketOrderEntry>,
+    pub my_orders: Vec<MarketMyOrderEntry>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum MarketOrderKind {
+    Sell,
+    Buy,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarketOrderEntry {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub cells_texts: HashMap<String, String>,
+    pub price: Option<f64>,
+    pub quantity: Option<i64>,
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarketMyOrderEntry {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub cells_texts: HashMap<String, String>,
+    pub kind: MarketOrderKind,
+    pub type_name: Option<String>,
+    pub price: Option<f64>,
+    pub volume_remaining: Option<i64>,
+    pub volume_entered: Option<i64>,
+    pub station: Option<String>,
+    pub is_outbid: bool,
+    pub best_competing_price: Option<f64>,
+}
@@ -0,0 +1,14 @@
+﻿use std::sync::Arc;
+use serde::Serialize;
+use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+#[derive(Debug, Serialize)]
+pub struct UiControl {
+    #[serde(skip_serializing)]
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub name: String,
+    pub text: Option<String>,
+    pub is_checked: Option<bool>,
+    pub display_region: DisplayRegion,
+}
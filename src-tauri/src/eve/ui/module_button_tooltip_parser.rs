@@ -0,0 +1,115 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::eve::ui::models::module_button_tooltip::ModuleButtonTooltip;
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+
+const ROW_VERTICAL_TOLERANCE: i32 = 6;
+const MAX_TOOLTIP_TO_MODULE_BUTTON_DISTANCE: i32 = 80;
+
+lazy_static! {
+    static ref SHORTCUT_REGEX: Regex = Regex::new(r"(?i)^(?:(?:ctrl|alt|shift)\s*[-+]\s*)*f\d{1,2}$").unwrap();
+}
+
+impl ModuleButtonTooltip {
+
+    pub fn parse_module_button_tooltip(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Option<Arc<ModuleButtonTooltip>> {
+        let tooltip = zones.get(&UiZonesEnum::ModuleButtonTooltip)?.first()?;
+        let module_buttons = zones
+            .get(&UiZonesEnum::ModuleButton)
+            .cloned()
+            .unwrap_or_default();
+
+        Some(Arc::new(ModuleButtonTooltip::parse(Arc::clone(tooltip), &module_buttons)))
+    }
+
+    pub fn parse(
+        region_node: Arc<UITreeNodeWithDisplayRegion>,
+        module_buttons: &[Arc<UITreeNodeWithDisplayRegion>],
+    ) -> ModuleButtonTooltip {
+        let rows = ParserUtils::get_text_rows(&region_node, ROW_VERTICAL_TOLERANCE);
+
+        let module_button = ModuleButtonTooltip::find_module_button_under_tooltip(&region_node, module_buttons);
+
+        let shortcut = rows
+            .iter()
+            .flat_map(|row| row.split_whitespace())
+            .find(|word| SHORTCUT_REGEX.is_match(word))
+            .map(|word| word.to_uppercase());
+
+        let optimal_range_in_meters = ModuleButtonTooltip::find_row_value(&rows, t!("optimal_range").as_ref())
+            .and_then(ModuleButtonTooltip::value_in_meters);
+        let falloff_in_meters = ModuleButtonTooltip::find_row_value(&rows, t!("falloff").as_ref())
+            .and_then(ModuleButtonTooltip::value_in_meters);
+        let cycle_time_in_seconds = ModuleButtonTooltip::find_row_value(&rows, t!("cycle_time").as_ref())
+            .and_then(ModuleButtonTooltip::value_in_seconds);
+        let capacitor_use_in_gj = ModuleButtonTooltip::find_row_value(&rows, t!("capacitor_use").as_ref())
+            .filter(|(_, unit)| unit == "gj")
+            .map(|(value, _)| value);
+
+        ModuleButtonTooltip {
+            ui_node: region_node,
+            module_button_region: module_button
+                .as_ref()
+                .map(|button| button.total_display_region.as_ref().clone()),
+            module_button,
+            rows,
+            shortcut,
+            optimal_range_in_meters,
+            falloff_in_meters,
+            cycle_time_in_seconds,
+            capacitor_use_in_gj,
+        }
+    }
+
+    fn find_module_button_under_tooltip(
+        tooltip_node: &Arc<UITreeNodeWithDisplayRegion>,
+        module_buttons: &[Arc<UITreeNodeWithDisplayRegion>],
+    ) -> Option<Arc<UITreeNodeWithDisplayRegion>> {
+        let tooltip_region = &tooltip_node.total_display_region;
+        let tooltip_center_x = tooltip_region.x + tooltip_region.width / 2;
+
+        // The tooltip opens right above the hovered module and is horizontally centered on it.
+        // Tooltips of other UI elements share the layer, so a far away button is not a match.
+        module_buttons
+            .iter()
+            .map(|button| {
+                let button_region = &button.total_display_region;
+                let button_center_x = button_region.x + button_region.width / 2;
+                let distance = (tooltip_center_x - button_center_x).abs() + (button_region.y - tooltip_region.bottom()).abs();
+
+                (distance, button)
+            })
+            .filter(|(distance, _)| *distance <= MAX_TOOLTIP_TO_MODULE_BUTTON_DISTANCE)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, button)| Arc::clone(button))
+    }
+
+    fn find_row_value(rows: &[String], label: &str) -> Option<(f64, String)> {
+        let label = label.to_lowercase();
+
+        rows.iter()
+            .find(|row| row.to_lowercase().contains(&label))
+            .and_then(|row| ParserUtils::parse_number_with_unit(row))
+    }
+
+    fn value_in_meters((value, unit): (f64, String)) -> Option<i32> {
+        match unit.as_str() {
+            "m" => Some(value.round() as i32),
+            "km" => Some((value * 1000.0).round() as i32),
+            _ => None,
+        }
+    }
+
+    fn value_in_seconds((value, unit): (f64, String)) -> Option<f64> {
+        match unit.as_str() {
+            "ms" => Some(value / 1000.0),
+            "s" => Some(value),
+            "min" => Some(value * 60.0),
+            _ => None,
+        }
+    }
+}
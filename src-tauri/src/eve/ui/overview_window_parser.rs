@@ -5,7 +5,7 @@ use crate::eve::ui::parser_utils::ParserUtils;
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
 use std::collections::HashSet;
-use std::rc::Rc;
+use std::sync::Arc;
 use log::debug;
 use crate::eve::ui_tree_node::common::common::ColorComponents;
 use crate::eve::ui_tree_node::models::child_of_node::ChildWithRegion;
@@ -19,7 +19,7 @@ const FLAG_ICON_NEUTRAL: &str = "/UI/Texture/classes/FlagIcon/8/5.png";
 const FLAG_ICON_CRIMINAL: &str = "/UI/Texture/classes/FlagIcon/8/6.png";
 
 impl OverviewWindow {
-    pub fn parse(overview_window_node: Rc<UITreeNodeWithDisplayRegion>) -> OverviewWindow {
+    pub fn parse(overview_window_node: Arc<UITreeNodeWithDisplayRegion>) -> OverviewWindow {
         let descendant_display_region = DisplayRegionUtils::list_descendants_with_display_region(
             &overview_window_node.child_with_region,
         );
@@ -57,7 +57,7 @@ impl OverviewWindow {
             .map(|child_with_region| {
                 OverviewWindow::parse_overview_window_entry(
                     &entries_headers,
-                    Rc::clone(&child_with_region.node),
+                    Arc::clone(&child_with_region.node),
                 )
             })
             .collect();
@@ -71,14 +71,14 @@ impl OverviewWindow {
     }
 
     pub fn parse_overview_window_entry(
-        entry_headers: &Vec<(String, Rc<UITreeNodeWithDisplayRegion>)>,
-        overview_window_entry_node: Rc<UITreeNodeWithDisplayRegion>,
+        entry_headers: &Vec<(String, Arc<UITreeNodeWithDisplayRegion>)>,
+        overview_window_entry_node: Arc<UITreeNodeWithDisplayRegion>,
     ) -> OverviewWindowEntry {
         
 
         let list_view_entry = ParserUtils::parse_list_view_entry(
             entry_headers,
-            Rc::clone(&overview_window_entry_node),
+            Arc::clone(&overview_window_entry_node),
         );
         let texts_left_to_right = Self::extract_text_left_to_right(&overview_window_entry_node);
         
@@ -145,6 +145,10 @@ impl OverviewWindow {
         
 
         OverviewWindowEntry {
+            entity_id: None,
+            object_address: overview_window_entry_node.ui_node.object_address,
+            closing_speed_in_meters_per_second: None,
+            time_to_contact_in_seconds: None,
             ui_node: overview_window_entry_node,
             texts_left_to_right,
             cells_texts: list_view_entry,
@@ -163,7 +167,7 @@ impl OverviewWindow {
         }
     }
 
-    fn get_bg_color_fills_percent(overview_window_entry_node: &Rc<UITreeNodeWithDisplayRegion>) -> Vec<ColorComponents> {
+    fn get_bg_color_fills_percent(overview_window_entry_node: &Arc<UITreeNodeWithDisplayRegion>) -> Vec<ColorComponents> {
         let bg_color_fills_percent: Vec<_> = DisplayRegionUtils::list_descendants_with_display_region(
             &overview_window_entry_node.child_with_region,
         )
@@ -183,7 +187,7 @@ impl OverviewWindow {
         bg_color_fills_percent
     }
 
-    fn get_right_aligned_icons_hints(overview_window_entry_node: &Rc<UITreeNodeWithDisplayRegion>) -> Vec<String> {
+    fn get_right_aligned_icons_hints(overview_window_entry_node: &Arc<UITreeNodeWithDisplayRegion>) -> Vec<String> {
         let right_aligned_icons_hints: Vec<_> = DisplayRegionUtils::list_descendants_with_display_region(
             &overview_window_entry_node.child_with_region,
         )
@@ -212,7 +216,7 @@ impl OverviewWindow {
         right_aligned_icons_hints
     }
 
-    fn get_icon_sprite_color_perfect(space_object_icon_descendants: &Option<Vec<Rc<ChildWithRegion>>>) -> Option<ColorComponents> {
+    fn get_icon_sprite_color_perfect(space_object_icon_descendants: &Option<Vec<Arc<ChildWithRegion>>>) -> Option<ColorComponents> {
         let icon_sprite_color_percent = space_object_icon_descendants
             .as_ref() // Nos aseguramos de trabajar con una referencia opcional.
             .and_then(|descendants| {
@@ -243,7 +247,7 @@ impl OverviewWindow {
         FLAG_ICONS.iter().any(|&icon| s.contains(icon))
     }
 
-    fn is_player(space_object_icon_descendants: Option<Vec<Rc<ChildWithRegion>>>) -> bool {
+    fn is_player(space_object_icon_descendants: Option<Vec<Arc<ChildWithRegion>>>) -> bool {
         if space_object_icon_descendants.is_none() {
             return false;
         }
@@ -295,7 +299,7 @@ impl OverviewWindow {
     }
 
     fn extract_text_left_to_right(
-        overview_window_entry_node: &Rc<UITreeNodeWithDisplayRegion>,
+        overview_window_entry_node: &Arc<UITreeNodeWithDisplayRegion>,
     ) -> Vec<String> {
         let mut all_text_with_regions =
             ParserUtils::get_all_contained_display_texts_with_region(&overview_window_entry_node);
@@ -1,19 +1,24 @@
 ﻿use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
+use lazy_static::lazy_static;
 use regex::Regex;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::ui_control::UiControl;
 use crate::eve::ui_tree_node::common::common::ColorComponents;
 use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
 use crate::eve::ui_tree_node::models::ui_tree_node::{ScrollControls, UITreeNodeWithDisplayRegion, UiTreeNode};
 use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
 
+pub const KILOMETERS_PER_AU: f64 = 149_597_870.7;
+
 pub struct ParserUtils{}
 
 impl ParserUtils {
     pub fn parse_list_view_entry(
-        entry_headers: &Vec<(String, Rc<UITreeNodeWithDisplayRegion>)>,
-        list_view_entry_node: Rc<UITreeNodeWithDisplayRegion>,
+        entry_headers: &Vec<(String, Arc<UITreeNodeWithDisplayRegion>)>,
+        list_view_entry_node: Arc<UITreeNodeWithDisplayRegion>,
     ) -> HashMap<String, String> {
         /*
         Observations show two different kinds of representations of the texts in the cells in a list view:
@@ -64,9 +69,35 @@ impl ParserUtils {
         cells_texts
     }
     
+    pub fn get_text_rows(
+        ui_node: &Arc<UITreeNodeWithDisplayRegion>,
+        vertical_tolerance: i32,
+    ) -> Vec<String> {
+        let mut texts = ParserUtils::get_all_contained_display_texts_with_region(ui_node)
+            .into_iter()
+            .map(|(text, node)| (HtmlParserUtils::strip_tags(&text), Arc::clone(&node.total_display_region)))
+            .filter(|(text, _)| !text.is_empty())
+            .collect::<Vec<_>>();
+
+        texts.sort_by_key(|(_, region)| (region.y, region.x));
+
+        // Labels and values are usually separate nodes, texts on the same line make up one row.
+        let mut rows: Vec<(i32, Vec<String>)> = Vec::new();
+        for (text, region) in texts {
+            match rows.last_mut() {
+                Some((row_y, row_texts)) if (region.y - *row_y).abs() <= vertical_tolerance => {
+                    row_texts.push(text)
+                }
+                _ => rows.push((region.y, vec![text])),
+            }
+        }
+
+        rows.into_iter().map(|(_, row_texts)| row_texts.join(" ")).collect()
+    }
+
     pub fn get_all_contained_display_texts_with_region(
-        ui_node: &Rc<UITreeNodeWithDisplayRegion>,
-    ) -> Vec<(String, Rc<UITreeNodeWithDisplayRegion>)> {
+        ui_node: &Arc<UITreeNodeWithDisplayRegion>,
+    ) -> Vec<(String, Arc<UITreeNodeWithDisplayRegion>)> {
         let mut result = Vec::new();
         
             let descendant_children = DisplayRegionUtils::list_descendants_with_display_region(&ui_node.child_with_region);
@@ -83,7 +114,7 @@ impl ParserUtils {
         result
     }
 
-    fn header_region_matches_cell_region(
+    pub(crate) fn header_region_matches_cell_region(
         header_region: &DisplayRegion,
         cell_region: &DisplayRegion,
     ) -> bool {
@@ -91,7 +122,7 @@ impl ParserUtils {
             && (header_region.x + header_region.width > cell_region.x + cell_region.width - 3)
     }
 
-    pub fn parse_scroll_controls(scroll_node: &Rc<UITreeNodeWithDisplayRegion>) -> ScrollControls {
+    pub fn parse_scroll_controls(scroll_node: &Arc<UITreeNodeWithDisplayRegion>) -> ScrollControls {
         let scroll_handle = DisplayRegionUtils::list_descendants_with_display_region(&scroll_node.child_with_region)
             .into_iter()
             .find(|node| node.node.ui_node.object_type_name == "ScrollHandle");
@@ -102,6 +133,48 @@ impl ParserUtils {
         }
     }
 
+    pub fn parse_control(node: &Arc<UITreeNodeWithDisplayRegion>) -> Option<UiControl> {
+        const CONTROL_TYPE_HINTS: [&str; 5] = ["button", "combo", "slider", "edit", "checkbox"];
+
+        let type_name = node.ui_node.object_type_name.to_lowercase();
+        if !CONTROL_TYPE_HINTS.iter().any(|hint| type_name.contains(hint)) {
+            return None;
+        }
+
+        let name = ParserUtils::get_name_from_dict_entries(&node.ui_node)?;
+
+        let text = std::iter::once(UiTreeNode::get_display_text(&node.ui_node))
+            .chain(
+                ParserUtils::get_all_contained_display_texts_with_region(node)
+                    .into_iter()
+                    .map(|(text, _)| text),
+            )
+            .map(|text| HtmlParserUtils::strip_tags(&text))
+            .find(|text| !text.is_empty());
+
+        let is_checked = std::iter::once(&node.ui_node)
+            .chain(
+                DisplayRegionUtils::list_descendants_with_display_region(&node.child_with_region)
+                    .iter()
+                    .map(|child| &child.node.ui_node),
+            )
+            .find_map(|ui_node| {
+                ui_node
+                    .dict_entries_of_interest
+                    .get("_checked")
+                    .and_then(|value| value.downcast_ref::<bool>())
+                    .copied()
+            });
+
+        Some(UiControl {
+            ui_node: Arc::clone(node),
+            name,
+            text,
+            is_checked,
+            display_region: node.total_display_region.as_ref().clone(),
+        })
+    }
+
     pub fn parse_distance_in_meters_from_text(
         distance_display_text_before_trim: &Option<String>,
     ) -> Option<i32> {
@@ -153,6 +226,89 @@ impl ParserUtils {
         number_text.parse::<i32>().map_err(|e| format!("Failed to parse number: {}", e))
     }
 
+    pub fn parse_isk_amount(amount_text: &str) -> Option<f64> {
+        ParserUtils::parse_decimal_number(&amount_text.replace("ISK", ""))
+    }
+
+    pub fn parse_decimal_number(number_text: &str) -> Option<f64> {
+        // Depending on the client language a number reads "1.234.567,89" or "1,234,567.89".
+        let number_text: String = number_text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        let separators: Vec<(usize, char)> = number_text
+            .char_indices()
+            .filter(|(_, c)| *c == '.' || *c == ',')
+            .collect();
+
+        let decimal_separator_index = separators.last().and_then(|&(index, separator)| {
+            let occurrences = separators.iter().filter(|(_, c)| *c == separator).count();
+            let digits_after = number_text.len() - index - 1;
+            // "0.125" has no integer digits to group, so its separator can only be decimal.
+            let integer_part = number_text[..index].trim_start_matches(['-', '+']);
+            let groups_thousands = digits_after == 3 && !integer_part.chars().all(|c| c == '0');
+
+            if occurrences == separators.len() && (occurrences > 1 || groups_thousands) {
+                None
+            } else {
+                Some(index)
+            }
+        });
+
+        let normalized: String = number_text
+            .char_indices()
+            .filter_map(|(i, c)| match c {
+                '.' | ',' if Some(i) == decimal_separator_index => Some('.'),
+                '.' | ',' => None,
+                _ => Some(c),
+            })
+            .collect();
+
+        normalized.parse::<f64>().ok()
+    }
+
+    pub fn parse_number_with_unit(text: &str) -> Option<(f64, String)> {
+        NUMBER_WITH_UNIT_REGEX.captures_iter(text).last().and_then(|captures| {
+            let number = ParserUtils::parse_decimal_number(&captures[1])?;
+            Some((number, captures[2].to_lowercase()))
+        })
+    }
+
+    pub fn parse_duration_in_seconds(duration_text: &str) -> Option<i64> {
+        // Timers read either "1d 4h 13m 2s" or "04:13:02".
+        if let Some(captures) = DURATION_CLOCK_REGEX.captures(duration_text) {
+            let hours = captures.get(1).map_or(0, |m| m.as_str().parse::<i64>().unwrap_or(0));
+            let minutes = captures[2].parse::<i64>().ok()?;
+            let seconds = captures[3].parse::<i64>().ok()?;
+            return Some(hours * 3600 + minutes * 60 + seconds);
+        }
+
+        let mut total_seconds = None;
+        for captures in DURATION_COMPONENTS_REGEX.captures_iter(duration_text) {
+            let value = captures[1].parse::<i64>().ok()?;
+            let unit_in_seconds = match captures[2].to_lowercase().as_str() {
+                "d" => 86400,
+                "h" => 3600,
+                "m" => 60,
+                _ => 1,
+            };
+            total_seconds = Some(total_seconds.unwrap_or(0) + value * unit_in_seconds);
+        }
+
+        total_seconds
+    }
+
+    pub fn parse_quantity(quantity_text: &str) -> Option<i64> {
+        let digits: String = quantity_text.chars().filter(|c| c.is_ascii_digit()).collect();
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        digits.parse::<i64>().ok()
+    }
+
     pub fn get_name_from_dict_entries(ui_tree_node: &UiTreeNode) -> Option<String> {
         ParserUtils::get_string_property_from_dict_entries(ui_tree_node, "_name")
     }
@@ -174,7 +330,7 @@ impl ParserUtils {
         ui_tree_node: &UiTreeNode,
     ) -> Option<ColorComponents>{
         if let Some(object_value) = ui_tree_node.dict_entries_of_interest.get("_color") {
-           // if let Some(boxed_any) = object_value.clone().downcast_ref::<Box<dyn Any>>() {
+           // if let Some(boxed_any) = object_value.clone().downcast_ref::<Box<dyn Any + Send + Sync>>() {
                 if let Some(color_components) = object_value.downcast_ref::<ColorComponents>() {
                     return Some(color_components.clone());
                 }
@@ -188,7 +344,7 @@ impl ParserUtils {
         property_name: &str,
     ) -> Option<String> {
         if let Some(object_value) = ui_tree_node.dict_entries_of_interest.get(property_name) {
-            //if let Some(boxed_any) = object_value.clone().downcast_ref::<Box<dyn Any>>() {
+            //if let Some(boxed_any) = object_value.clone().downcast_ref::<Box<dyn Any + Send + Sync>>() {
                 if let Some(string_property) = object_value.downcast_ref::<String>() {
                     return Some(string_property.clone());
                 }
@@ -207,4 +363,77 @@ impl ParserUtils {
     }
     
     
-}
\ No newline at end of file
+}
+
+lazy_static! {
+    static ref NUMBER_WITH_UNIT_REGEX: Regex = Regex::new(r"(?i)(\d[\d.,\s]*?)\s*(km|au|ms|min|gj|m|s)\b").unwrap();
+    static ref DURATION_CLOCK_REGEX: Regex = Regex::new(r"(?:(\d+):)?(\d{1,2}):(\d{2})").unwrap();
+    static ref DURATION_COMPONENTS_REGEX: Regex = Regex::new(r"(?i)(\d+)\s*(d|h|m|s)\b").unwrap();
+}
+#[cfg(test)]
+mod tests {
+    use crate::eve::ui::parser_utils::ParserUtils;
+
+    #[test]
+    fn parses_either_thousands_and_decimal_convention() {
+        assert_eq!(ParserUtils::parse_decimal_number("1.234.567,89"), Some(1234567.89));
+        assert_eq!(ParserUtils::parse_decimal_number("1,234,567.89"), Some(1234567.89));
+        assert_eq!(ParserUtils::parse_decimal_number("1 234 567,89"), Some(1234567.89));
+    }
+
+    #[test]
+    fn reads_a_single_separator_before_three_digits_as_thousands() {
+        assert_eq!(ParserUtils::parse_decimal_number("2.250"), Some(2250.0));
+        assert_eq!(ParserUtils::parse_decimal_number("12,500"), Some(12500.0));
+    }
+
+    #[test]
+    fn reads_a_single_separator_after_a_zero_integer_part_as_decimal() {
+        assert_eq!(ParserUtils::parse_decimal_number("0.125"), Some(0.125));
+        assert_eq!(ParserUtils::parse_decimal_number("0,125"), Some(0.125));
+        assert_eq!(ParserUtils::parse_decimal_number(".125"), Some(0.125));
+        assert_eq!(ParserUtils::parse_decimal_number("-0,500"), Some(-0.5));
+    }
+
+    #[test]
+    fn reads_a_single_separator_before_other_digit_counts_as_decimal() {
+        assert_eq!(ParserUtils::parse_decimal_number("12,5"), Some(12.5));
+        assert_eq!(ParserUtils::parse_decimal_number("3.75"), Some(3.75));
+        assert_eq!(ParserUtils::parse_decimal_number("-5.0"), Some(-5.0));
+    }
+
+    #[test]
+    fn parses_numbers_without_separators() {
+        assert_eq!(ParserUtils::parse_decimal_number("42"), Some(42.0));
+        assert_eq!(ParserUtils::parse_decimal_number(""), None);
+        assert_eq!(ParserUtils::parse_decimal_number("abc"), None);
+    }
+
+    #[test]
+    fn parses_the_last_number_with_its_unit() {
+        assert_eq!(ParserUtils::parse_number_with_unit("Optimal range: 12,5 km"), Some((12.5, "km".to_string())));
+        assert_eq!(ParserUtils::parse_number_with_unit("2 AU"), Some((2.0, "au".to_string())));
+        assert_eq!(ParserUtils::parse_number_with_unit("Duration 0.125 s"), Some((0.125, "s".to_string())));
+        assert_eq!(ParserUtils::parse_number_with_unit("from 1 km to 3.000 m"), Some((3000.0, "m".to_string())));
+        assert_eq!(ParserUtils::parse_number_with_unit("no unit 12"), None);
+    }
+
+    #[test]
+    fn parses_duration_components() {
+        assert_eq!(ParserUtils::parse_duration_in_seconds("1d 4h 13m 2s"), Some(101582));
+        assert_eq!(ParserUtils::parse_duration_in_seconds("13m"), Some(780));
+        assert_eq!(ParserUtils::parse_duration_in_seconds("45 S"), Some(45));
+    }
+
+    #[test]
+    fn parses_clock_durations() {
+        assert_eq!(ParserUtils::parse_duration_in_seconds("04:13:02"), Some(15182));
+        assert_eq!(ParserUtils::parse_duration_in_seconds("Expires in 13:02"), Some(782));
+    }
+
+    #[test]
+    fn no_duration_without_a_timer() {
+        assert_eq!(ParserUtils::parse_duration_in_seconds("Expired"), None);
+        assert_eq!(ParserUtils::parse_duration_in_seconds(""), None);
+    }
+}
@@ -0,0 +1,182 @@
+﻿use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::planetary_colony::{PlanetExtractor, PlanetStorageContent, PlanetStorageFacility, PlanetaryColony};
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::child_of_node::ChildWithRegion;
+use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
+
+const ROW_VERTICAL_TOLERANCE: i32 = 6;
+
+impl PlanetaryColony {
+
+    pub fn parse_planetary_colony(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Option<Arc<PlanetaryColony>> {
+        let planetary_colony = zones.get(&UiZonesEnum::PlanetaryColony)?.first()?;
+
+        Some(Arc::new(PlanetaryColony::parse(Arc::clone(planetary_colony))))
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> PlanetaryColony {
+        let descendants =
+            DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
+
+        let planet_name = descendants
+            .iter()
+            .find(|child| child.node.ui_node.object_type_name == "WindowCaption")
+            .map(|child| HtmlParserUtils::strip_tags(&UiTreeNode::get_display_text(&child.node.ui_node)))
+            .filter(|text| !text.is_empty())
+            .or_else(|| {
+                ParserUtils::get_text_rows(&region_node, ROW_VERTICAL_TOLERANCE)
+                    .into_iter()
+                    .next()
+            });
+
+        let extractors = PlanetaryColony::outermost_nodes(&descendants, |type_name| {
+            type_name.contains("Extractor") && !type_name.contains("Head")
+        })
+        .into_iter()
+        .map(PlanetaryColony::parse_extractor)
+        .collect::<Vec<_>>();
+
+        let storage_facilities = PlanetaryColony::outermost_nodes(&descendants, |type_name| {
+            type_name.contains("Storage")
+        })
+        .into_iter()
+        .map(PlanetaryColony::parse_storage_facility)
+        .collect();
+
+        let launchpad = PlanetaryColony::outermost_nodes(&descendants, |type_name| {
+            type_name.contains("Launchpad")
+        })
+        .into_iter()
+        .next()
+        .map(PlanetaryColony::parse_storage_facility);
+
+        let has_expired_extractors = extractors.iter().any(|extractor| extractor.is_expired);
+
+        PlanetaryColony {
+            ui_node: region_node,
+            planet_name,
+            extractors,
+            storage_facilities,
+            launchpad,
+            has_expired_extractors,
+        }
+    }
+
+    fn outermost_nodes<F>(
+        descendants: &[Arc<ChildWithRegion>],
+        type_name_matches: F,
+    ) -> Vec<Arc<UITreeNodeWithDisplayRegion>>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let matching_nodes = descendants
+            .iter()
+            .filter(|child| type_name_matches(&child.node.ui_node.object_type_name))
+            .map(|child| Arc::clone(&child.node))
+            .collect::<Vec<_>>();
+
+        let nested_addresses: HashSet<u64> = matching_nodes
+            .iter()
+            .flat_map(|node| DisplayRegionUtils::list_descendants_with_display_region(&node.child_with_region))
+            .map(|child| child.node.ui_node.object_address)
+            .collect();
+
+        matching_nodes
+            .into_iter()
+            .filter(|node| !nested_addresses.contains(&node.ui_node.object_address))
+            .collect()
+    }
+
+    fn parse_extractor(extractor_node: Arc<UITreeNodeWithDisplayRegion>) -> PlanetExtractor {
+        let rows = ParserUtils::get_text_rows(&extractor_node, ROW_VERTICAL_TOLERANCE);
+
+        let expired_text = t!("expired").to_lowercase();
+        let shows_expired = rows.iter().any(|row| row.to_lowercase().contains(&expired_text));
+
+        let remaining_cycle_time_in_seconds = if shows_expired {
+            Some(0)
+        } else {
+            rows.iter().find_map(|row| ParserUtils::parse_duration_in_seconds(row))
+        };
+
+        let head_count = DisplayRegionUtils::list_descendants_with_display_region(&extractor_node.child_with_region)
+            .iter()
+            .filter(|child| child.node.ui_node.object_type_name.contains("Head"))
+            .count();
+
+        PlanetExtractor {
+            ui_node: extractor_node,
+            rows,
+            head_count: if head_count > 0 { Some(head_count) } else { None },
+            remaining_cycle_time_in_seconds,
+            is_expired: remaining_cycle_time_in_seconds == Some(0),
+        }
+    }
+
+    fn parse_storage_facility(storage_node: Arc<UITreeNodeWithDisplayRegion>) -> PlanetStorageFacility {
+        let rows = ParserUtils::get_text_rows(&storage_node, ROW_VERTICAL_TOLERANCE);
+
+        let name = rows.first().cloned();
+
+        // The capacity row reads "<used>/<capacity> m3", optionally behind a label.
+        let capacity_row_index = rows
+            .iter()
+            .position(|row| row.contains('/') && (row.contains("m3") || row.contains("m³")));
+
+        let (used_capacity_in_m3, capacity_in_m3) = match capacity_row_index {
+            Some(index) => {
+                let capacity_text = rows[index].replace("m³", "").replace("m3", "");
+                match capacity_text.split_once('/') {
+                    Some((used, total)) => {
+                        let used = used
+                            .rsplit(|c: char| c.is_alphabetic() || c == ':')
+                            .next()
+                            .unwrap_or(used);
+                        let total = total.split(|c: char| c.is_alphabetic()).next().unwrap_or(total);
+
+                        (ParserUtils::parse_decimal_number(used), ParserUtils::parse_decimal_number(total))
+                    }
+                    None => (None, None),
+                }
+            }
+            None => (None, None),
+        };
+
+        let contents = rows
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(index, _)| Some(*index) != capacity_row_index)
+            .filter_map(|(_, row)| PlanetaryColony::parse_storage_content(row))
+            .collect();
+
+        PlanetStorageFacility {
+            ui_node: storage_node,
+            name,
+            contents,
+            used_capacity_in_m3,
+            capacity_in_m3,
+        }
+    }
+
+    fn parse_storage_content(row: &str) -> Option<PlanetStorageContent> {
+        let (name, quantity) = match row.rsplit_once(' ') {
+            Some((name, quantity_text))
+                if quantity_text.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') =>
+            {
+                (name.trim().to_string(), ParserUtils::parse_quantity(quantity_text))
+            }
+            _ => (row.trim().to_string(), None),
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(PlanetStorageContent { name, quantity })
+    }
+}
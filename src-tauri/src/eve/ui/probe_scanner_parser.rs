@@ -1,25 +1,27 @@
 ﻿use std::collections::HashMap;
-use std::rc::Rc;
-use windows::Win32::UI::Shell::PARSEDURLA;
+use std::sync::Arc;
 use crate::eve::ui::models::directional_scanner::{DirectionalScanner, DirectionalScannerEntry};
-use crate::eve::ui::models::probe_scanner::{ProbeScanner, ProbeScannerEntry};
-use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui::html_parser_utils::HtmlParserUtils;
+use crate::eve::ui::models::probe_scanner::{ProbeScanner, ProbeScannerEntry, ProbeScannerProbe};
+use crate::eve::ui::parser_utils::{ParserUtils, KILOMETERS_PER_AU};
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
 use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
 
+const PROBE_ENTRY_TYPE: &str = "ProbeEntry";
+
 impl ProbeScanner {
 
-    pub fn parse_probe_scanner(zones: &HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>)-> Option<Rc<ProbeScanner>>{
+    pub fn parse_probe_scanner(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>)-> Option<Arc<ProbeScanner>>{
         let probe_scanner = zones.get(&UiZonesEnum::ProbeScanner);
         if probe_scanner.is_none() {
             return None;
         }
         let probe_scanner = ProbeScanner::parse(probe_scanner.unwrap()[0].clone());
 
-        Some(Rc::new(probe_scanner))
+        Some(Arc::new(probe_scanner))
     }
-    pub fn parse(region_node: Rc<UITreeNodeWithDisplayRegion>) -> ProbeScanner {
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> ProbeScanner {
 
         let childs_with_region =
             DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
@@ -44,19 +46,72 @@ impl ProbeScanner {
             }).filter(|entry| entry.is_some()).map(|entry| entry.unwrap()).collect::<Vec<_>>()
             
         } else {
-            return ProbeScanner {
-                ui_node: region_node,
-                entries: Vec::new(),
-            }
+            Vec::new()
         };
 
+        let probes = childs_with_region.iter()
+            .filter(|child| child.node.ui_node.object_type_name == PROBE_ENTRY_TYPE)
+            .filter_map(|child| ProbeScanner::extract_probe(&child.node))
+            .collect::<Vec<_>>();
+
+        let mut scan_button = None;
+        let mut formation_controls = Vec::new();
+        let mut range_controls = Vec::new();
+
+        // Names like "scanRangeCombo" mention "scan" too, so formation and range are matched first.
+        for control in childs_with_region.iter().filter_map(|child| ParserUtils::parse_control(&child.node)) {
+            let name = control.name.to_lowercase();
+            if name.contains("formation") {
+                formation_controls.push(control);
+            } else if name.contains("range") {
+                range_controls.push(control);
+            } else if scan_button.is_none() && (name.contains("analyze") || name.contains("scan")) {
+                scan_button = Some(control);
+            }
+        }
+
         ProbeScanner {
             ui_node: region_node,
             entries: list_node_with_entries,
+            probes,
+            scan_button,
+            formation_controls,
+            range_controls,
         }
     }
 
-    fn extract_entry(node: &Rc<UITreeNodeWithDisplayRegion>)-> Option<ProbeScannerEntry>{
+    fn extract_probe(node: &Arc<UITreeNodeWithDisplayRegion>) -> Option<ProbeScannerProbe> {
+        let texts = ParserUtils::get_all_contained_display_texts_with_region(node)
+            .into_iter()
+            .map(|(text, _)| HtmlParserUtils::strip_tags(&text))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>();
+
+        let name = texts.first()?.clone();
+
+        let range_with_value = texts.iter().skip(1).find_map(|text| {
+            match ParserUtils::parse_number_with_unit(text) {
+                Some((value, unit)) if unit == "au" => Some((text.clone(), value)),
+                Some((value, unit)) if unit == "km" => Some((text.clone(), value / KILOMETERS_PER_AU)),
+                _ => None,
+            }
+        });
+
+        let expiry_in_seconds = texts.iter()
+            .skip(1)
+            .filter(|text| text.contains(':'))
+            .find_map(|text| ParserUtils::parse_duration_in_seconds(text));
+
+        Some(ProbeScannerProbe {
+            ui_node: Arc::clone(node),
+            name,
+            range: range_with_value.as_ref().map(|(text, _)| text.clone()),
+            range_in_au: range_with_value.map(|(_, value)| value),
+            expiry_in_seconds,
+        })
+    }
+
+    fn extract_entry(node: &Arc<UITreeNodeWithDisplayRegion>)-> Option<ProbeScannerEntry>{
 
         let node_to_extract_info = node.child_with_region.get(1)
             .and_then(|child1| child1.node.child_with_region.get(0))
@@ -114,6 +169,15 @@ impl ProbeScanner {
             UiTreeNode::get_display_text(name_node)
         }else { "".parse().unwrap() };
 
+        let signal_strength = ParserUtils::get_all_contained_display_texts_with_region(node)
+            .into_iter()
+            .map(|(text, _)| HtmlParserUtils::strip_tags(&text))
+            .find(|text| text.ends_with('%'))
+            .unwrap_or_default();
+
+        let signal_strength_percent =
+            ParserUtils::parse_decimal_number(signal_strength.trim_end_matches('%'));
+
         
 
         if  type_site_icon.is_some()  {
@@ -122,10 +186,103 @@ impl ProbeScanner {
                 distance_unformatted: distance.to_string(),
                 id,
                 name,
-                signal_strength: "".parse().unwrap(),
+                signal_strength,
+                signal_strength_percent,
                 type_emplacement: tye_site,
             });
         }
         None
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::eve::ui::models::probe_scanner::ProbeScanner;
+    use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
+    use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+    fn node(entries: &[(&str, &str)], children: Vec<Arc<UITreeNodeWithDisplayRegion>>) -> Arc<UITreeNodeWithDisplayRegion> {
+        UITreeNodeWithDisplayRegion::for_test("Container", entries, DisplayRegion::new(0, 0, 10, 10), children)
+    }
+
+    fn text(text: &str) -> Arc<UITreeNodeWithDisplayRegion> {
+        node(&[("_setText", text)], Vec::new())
+    }
+
+    fn cell(content: Arc<UITreeNodeWithDisplayRegion>) -> Arc<UITreeNodeWithDisplayRegion> {
+        node(&[], vec![content])
+    }
+
+    /// A result row: the signal column first, then the type, name, id, distance and icon cells.
+    fn result_row(signal: &str, distance: &str) -> Arc<UITreeNodeWithDisplayRegion> {
+        let info = node(&[], vec![
+            cell(text("Cosmic Signature")),
+            cell(text("Unstable Wormhole")),
+            cell(text("ABC-123")),
+            cell(text(distance)),
+            cell(node(&[("_texturePath", "res:/ui/texture/icons/wormhole.png")], Vec::new())),
+        ]);
+
+        node(&[], vec![cell(text(signal)), cell(info)])
+    }
+
+    #[test]
+    fn reads_the_signal_strength_of_a_result() {
+        let entry = ProbeScanner::extract_entry(&result_row("<b>12,5%</b>", "850 km")).unwrap();
+
+        assert_eq!(entry.signal_strength, "12,5%");
+        assert_eq!(entry.signal_strength_percent, Some(12.5));
+        assert_eq!(entry.id, "ABC-123");
+        assert_eq!(entry.name, "Unstable Wormhole");
+        assert_eq!(entry.type_emplacement, "Cosmic Signature");
+        assert_eq!(entry.distance, Some(850_000));
+    }
+
+    #[test]
+    fn reads_a_fully_scanned_result() {
+        let entry = ProbeScanner::extract_entry(&result_row("100%", "12 km")).unwrap();
+
+        assert_eq!(entry.signal_strength_percent, Some(100.0));
+    }
+
+    #[test]
+    fn result_without_signal_text_has_no_strength() {
+        let entry = ProbeScanner::extract_entry(&result_row("", "12 km")).unwrap();
+
+        assert_eq!(entry.signal_strength, "");
+        assert_eq!(entry.signal_strength_percent, None);
+    }
+
+    #[test]
+    fn reads_probe_range_and_expiry() {
+        let probe = ProbeScanner::extract_probe(&node(&[], vec![
+            text("Core Scanner Probe I"),
+            text("0.125 AU"),
+            text("03:25"),
+        ]))
+        .unwrap();
+
+        assert_eq!(probe.name, "Core Scanner Probe I");
+        assert_eq!(probe.range.as_deref(), Some("0.125 AU"));
+        assert_eq!(probe.range_in_au, Some(0.125));
+        assert_eq!(probe.expiry_in_seconds, Some(205));
+    }
+
+    #[test]
+    fn converts_a_probe_range_in_kilometers_to_au() {
+        let probe = ProbeScanner::extract_probe(&node(&[], vec![
+            text("Combat Scanner Probe I"),
+            text("149.597.870,7 km"),
+        ]))
+        .unwrap();
+
+        assert_eq!(probe.range_in_au, Some(1.0));
+        assert_eq!(probe.expiry_in_seconds, None);
+    }
+
+    #[test]
+    fn probe_without_texts_is_skipped() {
+        assert!(ProbeScanner::extract_probe(&node(&[], Vec::new())).is_none());
+    }
+}
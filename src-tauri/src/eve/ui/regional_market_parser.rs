@@ -0,0 +1,365 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use crate::eve::ui::models::regional_market::{MarketMyOrderEntry, MarketOrderEntry, MarketOrderKind, RegionalMarket};
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::child_of_node::ChildWithRegion;
+use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
+
+const MARKET_ORDER_ENTRY_TYPE: &str = "MarketOrderEntry";
+const MY_ORDER_ENTRY_TYPE: &str = "MyOrdersEntry";
+const SELECTED_TYPE_NAME_LABEL: &str = "typeNameLabel";
+
+type ListHeaders = Vec<(String, Arc<UITreeNodeWithDisplayRegion>)>;
+
+impl RegionalMarket {
+
+    pub fn parse_regional_market(zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>) -> Option<Arc<RegionalMarket>> {
+        let regional_market = zones.get(&UiZonesEnum::RegionalMarket)?.first()?;
+
+        Some(Arc::new(RegionalMarket::parse(Arc::clone(regional_market))))
+    }
+
+    pub fn parse(region_node: Arc<UITreeNodeWithDisplayRegion>) -> RegionalMarket {
+        let descendants =
+            DisplayRegionUtils::list_descendants_with_display_region(&region_node.child_with_region);
+
+        let selected_type_name = descendants
+            .iter()
+            .find(|child| {
+                ParserUtils::get_name_from_dict_entries(&child.node.ui_node).as_deref() == Some(SELECTED_TYPE_NAME_LABEL)
+            })
+            .map(|child| UiTreeNode::get_display_text(&child.node.ui_node))
+            .filter(|text| !text.is_empty());
+
+        // Both tabs show the sell list above the buy list, so the vertical order tells them apart.
+        let mut market_order_lists = RegionalMarket::find_lists_with_entries(&descendants, MARKET_ORDER_ENTRY_TYPE)
+            .into_iter()
+            .map(|(headers, entries)| {
+                entries
+                    .into_iter()
+                    .map(|entry| RegionalMarket::parse_market_order_entry(&headers, entry))
+                    .collect::<Vec<_>>()
+            });
+        let sell_orders = market_order_lists.next().unwrap_or_default();
+        let buy_orders = market_order_lists.next().unwrap_or_default();
+
+        let my_orders = RegionalMarket::find_lists_with_entries(&descendants, MY_ORDER_ENTRY_TYPE)
+            .into_iter()
+            .zip([MarketOrderKind::Sell, MarketOrderKind::Buy])
+            .flat_map(|((headers, entries), kind)| {
+                entries
+                    .into_iter()
+                    .map(|entry| RegionalMarket::parse_my_order_entry(&headers, entry, kind))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut regional_market = RegionalMarket {
+            ui_node: region_node,
+            selected_type_name,
+            sell_orders,
+            buy_orders,
+            my_orders,
+        };
+
+        regional_market.flag_outbid_orders();
+
+        regional_market
+    }
+
+    fn find_lists_with_entries(
+        descendants: &[Arc<ChildWithRegion>],
+        entry_type: &str,
+    ) -> Vec<(ListHeaders, Vec<Arc<UITreeNodeWithDisplayRegion>>)> {
+        let mut lists = descendants
+            .iter()
+            .filter(|child| {
+                let type_name = child.node.ui_node.object_type_name.to_lowercase();
+                type_name.contains("basicdynamicscroll") || type_name == "scroll"
+            })
+            .map(|scroll| {
+                let scroll_descendants =
+                    DisplayRegionUtils::list_descendants_with_display_region(&scroll.node.child_with_region);
+
+                let headers = scroll_descendants
+                    .iter()
+                    .find(|node| node.node.ui_node.object_type_name.to_lowercase().contains("headers"))
+                    .map(|node| ParserUtils::get_all_contained_display_texts_with_region(&node.node))
+                    .unwrap_or_default();
+
+                let entries = scroll_descendants
+                    .iter()
+                    .filter(|node| node.node.ui_node.object_type_name == entry_type)
+                    .map(|node| Arc::clone(&node.node))
+                    .collect::<Vec<_>>();
+
+                (Arc::clone(&scroll.node), headers, entries)
+            })
+            .filter(|(_, _, entries)| !entries.is_empty())
+            .collect::<Vec<_>>();
+
+        lists.sort_by_key(|(scroll, _, _)| scroll.total_display_region.y);
+
+        lists.into_iter().map(|(_, headers, entries)| (headers, entries)).collect()
+    }
+
+    fn parse_market_order_entry(
+        headers: &ListHeaders,
+        entry_node: Arc<UITreeNodeWithDisplayRegion>,
+    ) -> MarketOrderEntry {
+        let cells_texts = ParserUtils::parse_list_view_entry(headers, Arc::clone(&entry_node));
+
+        let price = cells_texts
+            .get(t!("price").as_ref())
+            .and_then(|text| ParserUtils::parse_isk_amount(text));
+        let quantity = cells_texts
+            .get(t!("quantity").as_ref())
+            .and_then(|text| ParserUtils::parse_quantity(text));
+        let location = cells_texts.get(t!("location").as_ref()).cloned();
+
+        MarketOrderEntry {
+            ui_node: entry_node,
+            cells_texts,
+            price,
+            quantity,
+            location,
+        }
+    }
+
+    fn parse_my_order_entry(
+        headers: &ListHeaders,
+        entry_node: Arc<UITreeNodeWithDisplayRegion>,
+        kind: MarketOrderKind,
+    ) -> MarketMyOrderEntry {
+        let cells_texts = ParserUtils::parse_list_view_entry(headers, Arc::clone(&entry_node));
+
+        let type_name = cells_texts.get(t!("type").as_ref()).cloned();
+        let price = cells_texts
+            .get(t!("price").as_ref())
+            .and_then(|text| ParserUtils::parse_isk_amount(text));
+        let station = cells_texts.get(t!("station").as_ref()).cloned();
+
+        // The quantity column reads "remaining/entered", e.g. "150/200".
+        let (volume_remaining, volume_entered) = match cells_texts.get(t!("quantity").as_ref()) {
+            Some(text) => match text.split_once('/') {
+                Some((remaining, entered)) => (
+                    ParserUtils::parse_quantity(remaining),
+                    ParserUtils::parse_quantity(entered),
+                ),
+                None => (ParserUtils::parse_quantity(text), None),
+            },
+            None => (None, None),
+        };
+
+        MarketMyOrderEntry {
+            ui_node: entry_node,
+            cells_texts,
+            kind,
+            type_name,
+            price,
+            volume_remaining,
+            volume_entered,
+            station,
+            is_outbid: false,
+            best_competing_price: None,
+        }
+    }
+
+    fn flag_outbid_orders(&mut self) {
+        let selected_type_name = match &self.selected_type_name {
+            Some(selected_type_name) => selected_type_name,
+            None => return,
+        };
+
+        for my_order in self.my_orders.iter_mut() {
+            if my_order.type_name.as_ref() != Some(selected_type_name) {
+                continue;
+            }
+
+            let my_price = match my_order.price {
+                Some(price) => price,
+                None => continue,
+            };
+
+            // Sell orders only compete within the same station, buy orders compete across their range.
+            let best_competing_price = match my_order.kind {
+                MarketOrderKind::Sell => self
+                    .sell_orders
+                    .iter()
+                    .filter(|order| RegionalMarket::same_location(&my_order.station, &order.location))
+                    .filter_map(|order| order.price)
+                    .reduce(f64::min),
+                MarketOrderKind::Buy => self
+                    .buy_orders
+                    .iter()
+                    .filter_map(|order| order.price)
+                    .reduce(f64::max),
+            };
+
+            my_order.best_competing_price = best_competing_price;
+            my_order.is_outbid = match (my_order.kind, best_competing_price) {
+                (MarketOrderKind::Sell, Some(best)) => best < my_price,
+                (MarketOrderKind::Buy, Some(best)) => best > my_price,
+                _ => false,
+            };
+        }
+    }
+
+    fn same_location(station: &Option<String>, location: &Option<String>) -> bool {
+        match (station, location) {
+            (Some(station), Some(location)) => station == location,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::eve::ui::models::regional_market::{MarketMyOrderEntry, MarketOrderEntry, MarketOrderKind, RegionalMarket};
+    use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+
+    const JITA: &str = "Jita IV - Moon 4 - Caldari Navy Assembly Plant";
+    const AMARR: &str = "Amarr VIII (Oris) - Emperor Family Academy";
+
+    fn market_order(price: f64, location: &str) -> MarketOrderEntry {
+        MarketOrderEntry {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            cells_texts: HashMap::new(),
+            price: Some(price),
+            quantity: Some(10),
+            location: Some(location.to_string()),
+        }
+    }
+
+    fn my_order(kind: MarketOrderKind, type_name: &str, price: f64) -> MarketMyOrderEntry {
+        MarketMyOrderEntry {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            cells_texts: HashMap::new(),
+            kind,
+            type_name: Some(type_name.to_string()),
+            price: Some(price),
+            volume_remaining: Some(5),
+            volume_entered: Some(10),
+            station: Some(JITA.to_string()),
+            is_outbid: false,
+            best_competing_price: None,
+        }
+    }
+
+    fn flagged(
+        sell_orders: Vec<MarketOrderEntry>,
+        buy_orders: Vec<MarketOrderEntry>,
+        my_orders: Vec<MarketMyOrderEntry>,
+    ) -> Vec<(bool, Option<f64>)> {
+        let mut regional_market = RegionalMarket {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            selected_type_name: Some("Tritanium".to_string()),
+            sell_orders,
+            buy_orders,
+            my_orders,
+        };
+
+        regional_market.flag_outbid_orders();
+
+        regional_market
+            .my_orders
+            .iter()
+            .map(|order| (order.is_outbid, order.best_competing_price))
+            .collect()
+    }
+
+    #[test]
+    fn flags_sell_orders_undercut_in_the_same_station() {
+        let result = flagged(
+            vec![market_order(4.5, JITA), market_order(5.0, JITA), market_order(3.0, AMARR)],
+            Vec::new(),
+            vec![my_order(MarketOrderKind::Sell, "Tritanium", 5.0)],
+        );
+
+        assert_eq!(result, vec![(true, Some(4.5))]);
+    }
+
+    #[test]
+    fn cheaper_sell_orders_in_other_stations_do_not_undercut() {
+        let result = flagged(
+            vec![market_order(5.0, JITA), market_order(3.0, AMARR)],
+            Vec::new(),
+            vec![my_order(MarketOrderKind::Sell, "Tritanium", 5.0)],
+        );
+
+        assert_eq!(result, vec![(false, Some(5.0))]);
+    }
+
+    #[test]
+    fn flags_buy_orders_outbid_anywhere_in_the_list() {
+        let result = flagged(
+            Vec::new(),
+            vec![market_order(4.0, JITA), market_order(4.2, AMARR)],
+            vec![my_order(MarketOrderKind::Buy, "Tritanium", 4.0)],
+        );
+
+        assert_eq!(result, vec![(true, Some(4.2))]);
+    }
+
+    #[test]
+    fn matching_prices_are_not_outbid() {
+        let result = flagged(
+            vec![market_order(5.0, JITA)],
+            vec![market_order(4.0, JITA)],
+            vec![
+                my_order(MarketOrderKind::Sell, "Tritanium", 5.0),
+                my_order(MarketOrderKind::Buy, "Tritanium", 4.0),
+            ],
+        );
+
+        assert_eq!(result, vec![(false, Some(5.0)), (false, Some(4.0))]);
+    }
+
+    #[test]
+    fn only_orders_of_the_selected_item_are_compared() {
+        let result = flagged(
+            vec![market_order(4.5, JITA)],
+            Vec::new(),
+            vec![
+                my_order(MarketOrderKind::Sell, "Tritanium", 5.0),
+                my_order(MarketOrderKind::Sell, "Pyerite", 9.0),
+                my_order(MarketOrderKind::Sell, "Tritanium", 4.0),
+            ],
+        );
+
+        assert_eq!(result, vec![(true, Some(4.5)), (false, None), (false, Some(4.5))]);
+    }
+
+    #[test]
+    fn nothing_is_flagged_without_competing_orders() {
+        let result = flagged(
+            Vec::new(),
+            Vec::new(),
+            vec![
+                my_order(MarketOrderKind::Sell, "Tritanium", 5.0),
+                my_order(MarketOrderKind::Buy, "Tritanium", 4.0),
+            ],
+        );
+
+        assert_eq!(result, vec![(false, None), (false, None)]);
+    }
+
+    #[test]
+    fn nothing_is_flagged_without_a_selected_item() {
+        let mut regional_market = RegionalMarket {
+            ui_node: UITreeNodeWithDisplayRegion::empty(),
+            selected_type_name: None,
+            sell_orders: vec![market_order(1.0, JITA)],
+            buy_orders: Vec::new(),
+            my_orders: vec![my_order(MarketOrderKind::Sell, "Tritanium", 5.0)],
+        };
+
+        regional_market.flag_outbid_orders();
+
+        assert!(!regional_market.my_orders[0].is_outbid);
+        assert_eq!(regional_market.my_orders[0].best_competing_price, None);
+    }
+}
@@ -1,20 +1,20 @@
 ﻿use std::any::Any;
-use std::rc::Rc;
+use std::sync::Arc;
 use serde::Serialize;
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 
 pub trait ChildOfNodeWithDisplayRegion {
     fn has_region(&self) -> bool;
     fn as_any(&self) -> &dyn Any;
-    fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any>;
+    fn as_any_rc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
 }
 #[derive(Debug,Serialize)]
 pub struct ChildWithRegion {
-    pub node: Rc<UITreeNodeWithDisplayRegion>,
+    pub node: Arc<UITreeNodeWithDisplayRegion>,
 }
 #[derive(Debug ,Serialize)]
 pub struct ChildWithoutRegion {
-    pub node: Rc<UiTreeNode>,
+    pub node: Arc<UiTreeNode>,
 }
 
 impl ChildOfNodeWithDisplayRegion for ChildWithRegion {
@@ -25,7 +25,7 @@ impl ChildOfNodeWithDisplayRegion for ChildWithRegion {
         self
     }
 
-    fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any> {
+    fn as_any_rc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
         self
     }
 }
@@ -38,7 +38,7 @@ impl ChildOfNodeWithDisplayRegion for ChildWithoutRegion {
         self
     }
 
-    fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any> {
+    fn as_any_rc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
         self
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 ﻿use std::any::Any;
 use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::sync::Arc;
 use log::debug;
 use serde::ser::SerializeMap;
 use serde::Serialize;
@@ -13,14 +13,14 @@ pub struct UiTreeNode {
     pub object_address: u64,
     pub object_type_name: String,
     #[serde(serialize_with = "serialize_dict_entries")]
-    pub dict_entries_of_interest: HashMap<String, Rc<Box<dyn Any>>>,
+    pub dict_entries_of_interest: HashMap<String, Arc<Box<dyn Any + Send + Sync>>>,
     #[serde(skip)]
     pub other_dict_entries_keys: Vec<String>,
-    pub children: Vec<Rc<UiTreeNode>>,
+    pub children: Vec<Arc<UiTreeNode>>,
 }
 
 fn serialize_dict_entries<S>(
-    entries: &HashMap<String, Rc<Box<dyn Any>>>,
+    entries: &HashMap<String, Arc<Box<dyn Any + Send + Sync>>>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
 where
@@ -66,9 +66,9 @@ impl UiTreeNode {
     pub fn new(
         object_address: u64,
         object_type_name: String,
-        dict_entries_of_interest: HashMap<String, Rc<Box<dyn std::any::Any>>>,
+        dict_entries_of_interest: HashMap<String, Arc<Box<dyn std::any::Any + Send + Sync>>>,
         other_dict_entries_keys: Vec<String>,
-        children: Vec<Rc<UiTreeNode>>,
+        children: Vec<Arc<UiTreeNode>>,
     ) -> UiTreeNode {
         UiTreeNode {
             object_address,
@@ -79,12 +79,12 @@ impl UiTreeNode {
         }
     }
 
-    pub fn list_descendants_in_ui_tree_node(parent: &Rc<UiTreeNode>) -> Vec<Rc<UiTreeNode>> {
-        let mut descendants: Vec<Rc<UiTreeNode>> = Vec::new();
+    pub fn list_descendants_in_ui_tree_node(parent: &Arc<UiTreeNode>) -> Vec<Arc<UiTreeNode>> {
+        let mut descendants: Vec<Arc<UiTreeNode>> = Vec::new();
 
-        let parent_iter = Rc::clone(parent);
+        let parent_iter = Arc::clone(parent);
         for child in &parent_iter.children {
-            descendants.push(Rc::clone(child));
+            descendants.push(Arc::clone(child));
             
             descendants.extend(UiTreeNode::list_descendants_in_ui_tree_node(
                 &child
@@ -94,7 +94,7 @@ impl UiTreeNode {
         descendants
     }
 
-    pub fn get_display_text(ui_node: &Rc<UiTreeNode>) -> String {
+    pub fn get_display_text(ui_node: &Arc<UiTreeNode>) -> String {
         let keys_to_search = vec!["_setText", "_text"];
         let mut longest_text = String::new();
 
@@ -109,7 +109,7 @@ impl UiTreeNode {
                         longest_text = text.to_string();
                     }
                 }
-                /*let string_value_result = Rc::downcast::<String>(text_value.clone());
+                /*let string_value_result = Arc::downcast::<String>(text_value.clone());
                 if  string_value_result.is_err() {
                     continue;
                 }else{
@@ -128,23 +128,64 @@ impl UiTreeNode {
 }
 #[derive(Debug, Serialize)]
 pub struct UITreeNodeWithDisplayRegion {
-    pub ui_node: Rc<UiTreeNode>,
+    pub ui_node: Arc<UiTreeNode>,
     #[serde(skip)]
-    pub child_with_region: Vec<Rc<ChildWithRegion>>,
+    pub child_with_region: Vec<Arc<ChildWithRegion>>,
     #[serde(skip)]
-    pub child_without_region: Vec<Rc<ChildWithoutRegion>>,
+    pub child_without_region: Vec<Arc<ChildWithoutRegion>>,
     #[serde(skip)]
-    pub self_display_region: Rc<DisplayRegion>,
+    pub self_display_region: Arc<DisplayRegion>,
     #[serde(skip)]
-    pub total_display_region: Rc<DisplayRegion>,
+    pub total_display_region: Arc<DisplayRegion>,
     #[serde(skip)]
     pub total_display_region_visible: DisplayRegion,
 }
 
 impl UITreeNodeWithDisplayRegion {}
 
+#[cfg(test)]
+impl UITreeNodeWithDisplayRegion {
+    /// Childless node with an empty region, for tests of parsed models.
+    pub fn empty() -> Arc<UITreeNodeWithDisplayRegion> {
+        Arc::new(UITreeNodeWithDisplayRegion {
+            ui_node: Arc::new(UiTreeNode::new(0, "Container".to_string(), HashMap::new(), Vec::new(), Vec::new())),
+            child_with_region: Vec::new(),
+            child_without_region: Vec::new(),
+            self_display_region: Arc::new(DisplayRegion::new(0, 0, 0, 0)),
+            total_display_region: Arc::new(DisplayRegion::new(0, 0, 0, 0)),
+            total_display_region_visible: DisplayRegion::new(0, 0, 0, 0),
+        })
+    }
+
+    /// Node holding string dict entries such as `_setText` over the given children, for parser tests.
+    pub fn for_test(
+        object_type_name: &str,
+        string_entries: &[(&str, &str)],
+        region: DisplayRegion,
+        children: Vec<Arc<UITreeNodeWithDisplayRegion>>,
+    ) -> Arc<UITreeNodeWithDisplayRegion> {
+        let dict_entries_of_interest = string_entries
+            .iter()
+            .map(|(key, value)| {
+                let value: Arc<Box<dyn Any + Send + Sync>> = Arc::new(Box::new(value.to_string()));
+                (key.to_string(), value)
+            })
+            .collect();
+        let ui_children = children.iter().map(|child| Arc::clone(&child.ui_node)).collect();
+
+        Arc::new(UITreeNodeWithDisplayRegion {
+            ui_node: Arc::new(UiTreeNode::new(0, object_type_name.to_string(), dict_entries_of_interest, Vec::new(), ui_children)),
+            child_with_region: children.into_iter().map(|node| Arc::new(ChildWithRegion { node })).collect(),
+            child_without_region: Vec::new(),
+            self_display_region: Arc::new(region.clone()),
+            total_display_region: Arc::new(region.clone()),
+            total_display_region_visible: region,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct ScrollControls {
-    pub ui_node: Rc<UITreeNodeWithDisplayRegion>,
-    pub scroll_handle: Option<Rc<UITreeNodeWithDisplayRegion>>,
+    pub ui_node: Arc<UITreeNodeWithDisplayRegion>,
+    pub scroll_handle: Option<Arc<UITreeNodeWithDisplayRegion>>,
 }
@@ -1,7 +1,6 @@
-﻿use std::cell::RefCell;
-use lazy_static::lazy_static;
+﻿use lazy_static::lazy_static;
 use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
 
 pub struct UiConstants;
@@ -15,17 +14,17 @@ impl UiConstants {
         overview_window
     }
 
-    pub fn initialize_mapper() -> HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>> {
+    pub fn initialize_mapper() -> HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>> {
         let mut mapper = HashMap::new();
         mapper.insert(UiZonesEnum::Overview, Vec::new());
         mapper
     }
     
-    pub fn check_and_insert_inportant_zone(important_zones: &RefCell<HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>>, type_id: &String, node_ref: Rc<UITreeNodeWithDisplayRegion>) {
+    pub fn check_and_insert_inportant_zone(important_zones: &Mutex<HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>>, type_id: &String, node_ref: Arc<UITreeNodeWithDisplayRegion>) {
         let ui_zones = UI_ZONES.get(&type_id.as_str());
         match ui_zones {
             Some(zone) => {
-                let mut important_zones = important_zones.borrow_mut();
+                let mut important_zones = important_zones.lock().unwrap();
                 let zone = important_zones.entry(zone.clone()).or_insert(Vec::new());
                 zone.push(node_ref.clone());
             },
@@ -41,6 +40,14 @@ pub enum UiZonesEnum {
     Overview,
     DirectionalScanner,
     ProbeScanner,
+    RegionalMarket,
+    AgentConversation,
+    MissionTracker,
+    ModuleButton,
+    ModuleButtonTooltip,
+    PlanetaryColony,
+    LocationInfo,
+    ChatWindow,
 }
 
 lazy_static! {
@@ -52,6 +59,18 @@ lazy_static! {
         hash_map.insert("OverviewWindowOld", UiZonesEnum::Overview);
         hash_map.insert("DirectionalScanner", UiZonesEnum::DirectionalScanner);
         hash_map.insert("ProbeScannerWindow", UiZonesEnum::ProbeScanner);
+        hash_map.insert("RegionalMarket", UiZonesEnum::RegionalMarket);
+        hash_map.insert("AgentDialogueWindow", UiZonesEnum::AgentConversation);
+        hash_map.insert("InfoPanelMissions", UiZonesEnum::MissionTracker);
+        hash_map.insert("InfoPanelAgency", UiZonesEnum::MissionTracker);
+        hash_map.insert("InfoPanelOpportunities", UiZonesEnum::MissionTracker);
+        hash_map.insert("ModuleButton", UiZonesEnum::ModuleButton);
+        hash_map.insert("ModuleButtonTooltip", UiZonesEnum::ModuleButtonTooltip);
+        hash_map.insert("PlanetWindow", UiZonesEnum::PlanetaryColony);
+        hash_map.insert("PlanetView", UiZonesEnum::PlanetaryColony);
+        hash_map.insert("InfoPanelLocationInfo", UiZonesEnum::LocationInfo);
+        hash_map.insert("XmppChatWindow", UiZonesEnum::ChatWindow);
+        hash_map.insert("ChatWindow", UiZonesEnum::ChatWindow);
         hash_map
     };
     
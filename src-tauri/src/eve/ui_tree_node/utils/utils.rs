@@ -2,7 +2,7 @@
 use lazy_static::lazy_static;
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::sync::Arc;
 use std::result;
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 
@@ -11,14 +11,14 @@ pub struct UiUtils;
 impl UiUtils {
     pub fn fixed_number_from_property_name(
         property_name: &str,
-        entries_of_interest: &HashMap<String, Rc<Box<dyn Any>>>,
+        entries_of_interest: &HashMap<String, Arc<Box<dyn Any + Send + Sync>>>,
     ) -> Option<i32> {
         entries_of_interest
             .get(property_name)
             .and_then(|json_value| extract_int_from_int_or_string(json_value))
     }
 
-    pub fn fixed_number_from_ui_node(property_name: &str, ui_node: &Rc<UiTreeNode>) -> Option<i32> {
+    pub fn fixed_number_from_ui_node(property_name: &str, ui_node: &Arc<UiTreeNode>) -> Option<i32> {
         let property_to_convert_option = ui_node
             .dict_entries_of_interest
             .get(property_name);
@@ -41,8 +41,8 @@ impl UiUtils {
     
     pub fn find_node_in_tree(
         final_node_type: &String,
-        parent_node: Rc<UITreeNodeWithDisplayRegion>,
-    ) -> Option<Rc<UITreeNodeWithDisplayRegion>> {
+        parent_node: Arc<UITreeNodeWithDisplayRegion>,
+    ) -> Option<Arc<UITreeNodeWithDisplayRegion>> {
         
         if *final_node_type == parent_node.ui_node.object_type_name {
             return Some(parent_node);
@@ -1,7 +1,7 @@
 ﻿use crate::eve::interop::memory::models::int_wrapper::IntWrapper;
 use std::any::Any;
 
-pub fn extract_int_from_int_or_string(object_value: &Box<dyn Any>) -> Option<i32> {
+pub fn extract_int_from_int_or_string(object_value: &Box<dyn Any + Send + Sync>) -> Option<i32> {
     if let Some(long_int) = object_value.downcast_ref::<IntWrapper>() {
         return long_int.get_i32();
     } else if let Some(&int_value) = object_value.downcast_ref::<i32>() {
@@ -1,11 +1,16 @@
-use crate::commands::get_process_id::{get_process_ids, start_tracker, stop_tracker};
+use crate::commands::get_process_id::{get_multi_client_state, get_process_ids, start_tracker, stop_tracker};
 use crate::commands::process_watcher::process_watcher;
+use crate::commands::schema::get_schema_version;
+use crate::commands::sightings::{get_last_seen, get_most_frequent_systems, get_ships_flown};
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 mod commands;
 mod operations;
 mod eve;
 mod db;
+mod alerts;
+mod diff;
+mod config;
 
 #[macro_use]
 extern crate rust_i18n;
@@ -21,7 +26,7 @@ pub fn run() {
             Ok(())
         })
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![get_process_ids,start_tracker,stop_tracker])
+        .invoke_handler(tauri::generate_handler![get_process_ids,start_tracker,stop_tracker,get_multi_client_state,get_last_seen,get_most_frequent_systems,get_ships_flown,get_schema_version])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -14,6 +14,9 @@ mod operations;
 mod eve;
 mod db;
 mod commands;
+mod alerts;
+mod diff;
+mod config;
 
 #[macro_use]
 extern crate rust_i18n;
@@ -30,6 +33,21 @@ fn main() {
 
     SimpleLogger::new().with_level(LevelFilter::Debug).init().unwrap();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli_output = match args.first().map(|arg| arg.as_str()) {
+        Some("history") => Some(commands::sightings::run_history_command(&args[1..])),
+        Some("schema-version") => Some(commands::schema::run_schema_version_command()),
+        Some("memory-stats") => Some(commands::memory_stats::run_memory_stats_command(&args[1..])),
+        _ => None,
+    };
+    if let Some(cli_output) = cli_output {
+        match cli_output {
+            Ok(output) => println!("{}", output),
+            Err(error) => eprintln!("{}", error),
+        }
+        return;
+    }
+
     info!("Starting Eve Tracker");
     
     
@@ -0,0 +1,100 @@
+﻿use std::time::{Duration, Instant};
+use crate::eve::interop::gui::windows_utils::WindowsUtils;
+
+/// The client shows "EVE - <character name>" once a character is logged in, and just "EVE" before.
+const WINDOW_TITLE_PREFIX: &str = "EVE - ";
+const RESOLVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Keeps track of which character is logged into a client. Rechecked periodically since
+/// the client can sit on character selection or switch characters without restarting.
+#[derive(Debug)]
+pub struct CharacterResolver {
+    character_name: Option<String>,
+    last_attempt: Option<Instant>,
+}
+
+impl CharacterResolver {
+
+    pub fn new() -> Self {
+        CharacterResolver {
+            character_name: None,
+            last_attempt: None,
+        }
+    }
+
+    pub fn character_name(&self) -> Option<&str> {
+        self.character_name.as_deref()
+    }
+
+    /// Rereads the window caption when due. Returns true when the character changed.
+    pub fn poll(&mut self, process_id: u32, now: Instant) -> bool {
+        if let Some(last_attempt) = self.last_attempt {
+            if now.duration_since(last_attempt) < RESOLVE_INTERVAL {
+                return false;
+            }
+        }
+        self.last_attempt = Some(now);
+
+        let character_name = WindowsUtils::get_window_title(process_id)
+            .and_then(|title| CharacterResolver::character_name_from_window_title(&title));
+
+        if character_name == self.character_name {
+            return false;
+        }
+
+        self.character_name = character_name;
+        true
+    }
+
+    pub fn character_name_from_window_title(title: &str) -> Option<String> {
+        let character_name = title.strip_prefix(WINDOW_TITLE_PREFIX)?.trim();
+
+        if character_name.is_empty() {
+            return None;
+        }
+
+        Some(character_name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::operations::character_resolver::CharacterResolver;
+
+    #[test]
+    fn reads_the_character_after_the_prefix() {
+        assert_eq!(
+            CharacterResolver::character_name_from_window_title("EVE - Some Pilot"),
+            Some("Some Pilot".to_string())
+        );
+    }
+
+    #[test]
+    fn trims_whitespace_around_the_character() {
+        assert_eq!(
+            CharacterResolver::character_name_from_window_title("EVE -  Some Pilot "),
+            Some("Some Pilot".to_string())
+        );
+    }
+
+    #[test]
+    fn no_character_before_login() {
+        assert_eq!(CharacterResolver::character_name_from_window_title("EVE"), None);
+        assert_eq!(CharacterResolver::character_name_from_window_title("EVE - "), None);
+        assert_eq!(CharacterResolver::character_name_from_window_title(""), None);
+    }
+
+    #[test]
+    fn ignores_other_windows() {
+        assert_eq!(CharacterResolver::character_name_from_window_title("EVE Launcher"), None);
+        assert_eq!(CharacterResolver::character_name_from_window_title("Notes - EVE - Some Pilot"), None);
+    }
+
+    #[test]
+    fn keeps_dashes_inside_the_character_name() {
+        assert_eq!(
+            CharacterResolver::character_name_from_window_title("EVE - Some-Pilot - Alt"),
+            Some("Some-Pilot - Alt".to_string())
+        );
+    }
+}
@@ -1,30 +1,60 @@
-﻿use std::collections::HashMap;
-use std::{fs, process, thread};
-use std::rc::Rc;
+﻿use std::collections::{HashMap, HashSet};
+use std::{fmt, fs, process, thread};
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::AtomicBool;
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use serde_json::to_string;
 use tauri::{Emitter, Window};
-use crate::db;
+use crate::alerts::alert_config::{AlertConfig, AlertConfigWatcher};
+use crate::alerts::alert_engine::AlertEngine;
+use crate::alerts::alert_event::AlertEvent;
+use crate::alerts::alert_rule::AlertRule;
+use crate::config::app_config::AppConfig;
+use crate::db::pool::DatabasePool;
+use crate::diff::closing_speed_tracker::ClosingSpeedTracker;
+use crate::diff::diff_event::DiffEvent;
+use crate::diff::frame_diff::FrameDiff;
+use crate::diff::overview_entity_tracker::OverviewEntityTracker;
 use crate::eve::interop::gui::windows_utils::WindowsUtils;
+use crate::eve::interop::memory::memory_reading_cache::MemoryReadingCacheStats;
+use crate::eve::interop::memory::models::memory_read_stats::MemoryReadStats;
 use crate::eve::ui::models::general_window::GeneralWindow;
 use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
 use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
 use crate::eve::ui_tree_node::utils::utils::UiUtils;
+use crate::db::database::Database;
+use crate::db::models::{CachedUiRootType, ProcessIdentity};
+use crate::operations::character_resolver::CharacterResolver;
 use crate::operations::extract_possible_root_address::ExtractPossibleRootAddress;
 use crate::operations::gui_simulation::GuiSimulation;
 use crate::operations::obtain_pid_process::ObtainPidProcess;
-use crate::operations::ui_tree_node_extractor::UiTreeNodeExtractor;
+use crate::operations::scan_progress::{CancellationToken, ProgressSink, RootAddressScanProgress};
+use crate::operations::sighting_recorder::SightingRecorder;
+use crate::operations::tracker_supervisor::{ClientState, TrackerSupervisor};
+use crate::operations::ui_root_candidate_scorer::{UiRootCandidateScore, UiRootCandidateScorer};
+use crate::operations::ui_tree_node_extractor::{ExtractionMode, UiTreeNodeExtractor};
 use serde::Serialize;
 
+const FULL_READS_PER_SECOND: u32 = 2;
+
 #[derive(Debug)]
 pub struct EveUiTracker {
     eve_ui_status: HashMap<u32,EveUiStatus>,
     running: Arc<AtomicBool>,
-    window: Arc<Mutex<Window>>
+    window: Arc<Mutex<Window>>,
+    supervisor: Arc<TrackerSupervisor>,
+    alert_engine: AlertEngine,
+    alert_config_watcher: AlertConfigWatcher,
+    alert_config: Option<AlertConfig>,
+    /// The config error currently shown in `EveUiStatus.error`, cleared once a valid config loads.
+    alert_config_error: Option<String>,
+    character_resolver: CharacterResolver,
+    frame_diff: FrameDiff,
+    overview_entity_tracker: OverviewEntityTracker,
+    closing_speed_tracker: ClosingSpeedTracker,
+    sighting_recorder: SightingRecorder,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -36,22 +66,88 @@ enum EveUiTrackerStatus {
 #[derive(Debug, Serialize, Clone)]
 pub struct EveUiStatus {
     pub process_id: u32,
+    pub character_name: Option<String>,
     pub status: EveUiTrackerStatus,
     pub error: Option<String>,
     pub general_window: Option<String>,
     pub ms_processing: u32,
+    pub alerts: Vec<AlertEvent>,
+    pub diff_events: Vec<DiffEvent>,
+    pub root_candidates: Vec<UiRootCandidateScore>,
+    pub memory_cache_stats: MemoryReadingCacheStats,
+    pub memory_read_stats: MemoryReadStats,
 }
 
 impl EveUiTracker {
     
-    pub fn new(window: Arc<Mutex<Window>>, running: Arc<AtomicBool>) -> Self {
+    pub fn new(supervisor: Arc<TrackerSupervisor>, running: Arc<AtomicBool>) -> Self {
         EveUiTracker {
             eve_ui_status: HashMap::new(),
             running,
-            window
+            window: supervisor.window(),
+            supervisor,
+            alert_engine: AlertEngine::new(AlertRule::default_rules(), HashSet::new()),
+            alert_config_watcher: AlertConfigWatcher::new(&AppConfig::global().alert_config_path),
+            alert_config: None,
+            alert_config_error: None,
+            character_resolver: CharacterResolver::new(),
+            frame_diff: FrameDiff::new(),
+            overview_entity_tracker: OverviewEntityTracker::new(),
+            closing_speed_tracker: ClosingSpeedTracker::new(),
+            sighting_recorder: SightingRecorder::new(),
+        }
+    }
+
+    fn reload_alert_config(&mut self, process: u32) {
+        let reloaded_config = match self.alert_config_watcher.poll() {
+            Some(reloaded_config) => reloaded_config,
+            None => return,
+        };
+
+        let eve_status = self.eve_ui_status.get_mut(&process).unwrap();
+
+        match reloaded_config {
+            Ok(alert_config) => {
+                info!("Alert config reloaded for process: {:?}", process);
+                self.alert_engine.apply_settings(alert_config.settings_for_character(self.character_resolver.character_name()));
+                self.alert_config = Some(alert_config);
+                let previous_error = self.alert_config_error.take();
+                if previous_error.is_some() && eve_status.error == previous_error {
+                    eve_status.error = None;
+                }
+            }
+            Err(error) => {
+                error!("Invalid alert config, keeping previous rules: {}", error);
+                eve_status.error = Some(error.clone());
+                self.alert_config_error = Some(error);
+            }
         }
     }
     
+    /// Moves alert cooldowns and per-character alert settings along when the logged in character changes.
+    fn resolve_character(&mut self, process: u32) {
+        let previous_character = self.character_resolver.character_name().map(str::to_string);
+        if !self.character_resolver.poll(process, Instant::now()) {
+            return;
+        }
+
+        let character_name = self.character_resolver.character_name().map(str::to_string);
+        info!("ProcessId: {:?}, character: {:?}", process, character_name);
+
+        let cooldowns = self.alert_engine.take_cooldowns();
+        if let Some(previous_character) = &previous_character {
+            self.supervisor.store_alert_cooldowns(previous_character, cooldowns);
+        }
+        if let Some(character_name) = &character_name {
+            self.alert_engine.restore_cooldowns(self.supervisor.take_alert_cooldowns(character_name));
+        }
+        if let Some(alert_config) = &self.alert_config {
+            self.alert_engine.apply_settings(alert_config.settings_for_character(character_name.as_deref()));
+        }
+
+        self.eve_ui_status.get_mut(&process).unwrap().character_name = character_name;
+    }
+
     fn send_error(&mut self, process: u32, error: String){
         let eve_status = self.eve_ui_status.get_mut(&process).unwrap();
         
@@ -61,16 +157,19 @@ impl EveUiTracker {
     }
    
     pub fn start_tracker(&mut self, process: u32) {
-        
-        
-        self.running.store(true, std::sync::atomic::Ordering::Relaxed);
 
         self.eve_ui_status.insert(process, EveUiStatus {
             process_id: process,
+            character_name: None,
             status: EveUiTrackerStatus::Running,
             error: None,
             general_window: None,
-            ms_processing: 0
+            ms_processing: 0,
+            alerts: Vec::new(),
+            diff_events: Vec::new(),
+            root_candidates: Vec::new(),
+            memory_cache_stats: MemoryReadingCacheStats::default(),
+            memory_read_stats: MemoryReadStats::default(),
         });
 
         info!("Starting tracker for process: {:?}", process);
@@ -93,6 +192,10 @@ impl EveUiTracker {
     fn stop_tracker(&mut self, process: u32){
 
         info!("the handle to stop");
+
+        if let Some(character_name) = self.character_resolver.character_name() {
+            self.supervisor.store_alert_cooldowns(character_name, self.alert_engine.take_cooldowns());
+        }
         
         let eve_status = self.eve_ui_status.get_mut(&process).unwrap();
 
@@ -103,10 +206,29 @@ impl EveUiTracker {
     
     fn modify_eve_ui_status(&mut self,
                             process: u32, 
-                            general_window: GeneralWindow){
+                            mut general_window: GeneralWindow){
+
+        let now = Instant::now();
+
+        self.overview_entity_tracker.assign_entity_ids(&mut general_window);
+        self.closing_speed_tracker.update(&mut general_window, now);
+
+        let alerts = self.alert_engine.evaluate(&general_window, now);
+        let diff_events = self.frame_diff.diff(&general_window);
+        let character_name = self.character_resolver.character_name().map(str::to_string);
+        self.sighting_recorder.record(&general_window, character_name.clone(), now);
+
+        let hostile_players = self.alert_engine.hostile_players(&general_window);
+        self.supervisor.report(ClientState::from_general_window(process, character_name, &general_window, hostile_players));
+
+        for alert in &alerts {
+            warn!("ProcessId: {:?}, Alert {}: {}", process, alert.rule_id, alert.message);
+        }
 
         let eve_status = self.eve_ui_status.get_mut(&process).unwrap();
         
+        eve_status.alerts = alerts;
+        eve_status.diff_events = diff_events;
         eve_status.general_window = Some(to_string(&general_window).unwrap());
         
     }
@@ -132,20 +254,34 @@ impl EveUiTracker {
     
     fn extract_ui_from_process(&mut self, process: u32){
 
-        let time_per_second: i32 = 2;
-        let interval = Duration::from_secs_f64(1.0 / time_per_second as f64);
+        let targeted_interval = Duration::from_secs_f64(1.0 / AppConfig::global().polls_per_second as f64);
+        let full_interval = Duration::from_secs_f64(1.0 / FULL_READS_PER_SECOND as f64);
         
-        let ui_tree_address = extract_ui_tree_address(process);
+        let progress_window = Arc::clone(&self.window);
+        let progress_sink: ProgressSink = Arc::new(move |progress: &RootAddressScanProgress| {
+            progress_window.lock().unwrap().emit("root_address_scan_progress", progress).ok();
+        });
+        let cancellation = CancellationToken::from_running_flag(Arc::clone(&self.running));
+
+        let ui_tree_address = self.extract_ui_tree_address(process, progress_sink, cancellation.clone());
 
-        if ui_tree_address.is_err() {
-            self.send_error(process, "Could not find ui tree address".to_string());
-            info!("Could not find ui tree address");
+        if cancellation.is_cancelled() {
+            info!("Root address search for process {:?} cancelled", process);
             return;
         }
-        let mut total_duration = Duration::new(0, 0);
-        let mut max_duration = Duration::new(0, 0);
-        let mut min_duration = Duration::new(u64::MAX, 0);
-        let mut count = 0;
+
+        let (ui_tree_address, root_candidates) = match ui_tree_address {
+            Ok(ui_tree_address) => ui_tree_address,
+            Err(error) => {
+                info!("Could not find ui tree address: {}", error);
+                self.send_error(process, error);
+                return;
+            }
+        };
+        self.eve_ui_status.get_mut(&process).unwrap().root_candidates = root_candidates;
+
+        let mut full_read_durations = ReadDurations::new();
+        let mut targeted_read_durations = ReadDurations::new();
 
         let mut last_print_time = Instant::now();
         
@@ -160,20 +296,25 @@ impl EveUiTracker {
             if (self.running.load(std::sync::atomic::Ordering::Relaxed) == false) {
                 return;
             }
+            self.resolve_character(process);
+            self.reload_alert_config(process);
+
             let start = Instant::now();
 
-            let ui_tree = ui_tree_node_extractor.extract_ui_tree_from_address(ui_tree_address.unwrap(), 99);
+            let zones = ui_tree_node_extractor.extract_zones_from_address(ui_tree_address, 99);
             let duration = start.elapsed();
-            if ui_tree.is_err() {
+            if zones.is_err() {
                 return;
             }
 
-            let ui_tree = ui_tree.unwrap();
-            let zones_with_ui = ui_tree.1;
+            let (zones_with_ui, extraction_mode) = zones.unwrap();
+            debug!("ProcessId: {:?}, {:?} read took {:?}", process, extraction_mode, duration);
             
             let general_window = GeneralWindow::parse_general_window(zones_with_ui.clone());
             
             self.modify_eve_ui_status(process, general_window);
+            let memory_cache_stats = ui_tree_node_extractor.memory_cache_stats();
+            let memory_read_stats = ui_tree_node_extractor.memory_read_stats();
 
             //let types = ui_tree.0.ui_node.extract_types();
 
@@ -181,96 +322,151 @@ impl EveUiTracker {
 
            // fs::write("archivo.txt", json).expect("TODO: panic message");
 
-            count += 1;
-            total_duration += duration;
-
-            // Update maximum and minimum durations
-            if duration > max_duration {
-                max_duration = duration;
-            }
-            if duration < min_duration {
-                min_duration = duration;
+            match extraction_mode {
+                ExtractionMode::Full => full_read_durations.add(duration),
+                ExtractionMode::ZoneTargeted => targeted_read_durations.add(duration),
             }
 
             // Print statistics every 3 seconds
             if last_print_time.elapsed() >= Duration::from_secs(3) {
-                let avg_duration = total_duration / count as u32;
-                info!("ProcessId: {:?}, Max: {:?}, Min: {:?}, Avg: {:?}, Times: {:?}",process, max_duration, min_duration, avg_duration, count);
+                info!("ProcessId: {:?}, Full reads: {}, Targeted reads: {}, Cache hit rate: {:.2}, Syscalls: {:?}, Syscalls saved: {:?}", process, full_read_durations, targeted_read_durations, memory_cache_stats.hit_rate, memory_read_stats.syscalls, memory_read_stats.syscalls_saved);
+                if let (Some(full_average), Some(targeted_average)) = (full_read_durations.average(), targeted_read_durations.average()) {
+                    info!("ProcessId: {:?}, Targeted reads are {:.1}x faster than full reads", process, full_average.as_secs_f64() / targeted_average.as_secs_f64().max(f64::EPSILON));
+                }
 
                 // Reset statistics
-                total_duration = Duration::new(0, 0);
-                max_duration = Duration::new(0, 0);
-                min_duration = Duration::new(u64::MAX, 0);
-                count = 0;
+                full_read_durations = ReadDurations::new();
+                targeted_read_durations = ReadDurations::new();
 
                 last_print_time = Instant::now();
             }
             
+            let eve_status = self.eve_ui_status.get_mut(&process).unwrap();
+            eve_status.memory_cache_stats = memory_cache_stats;
+            eve_status.memory_read_stats = memory_read_stats;
             self.send_event(process);
             
+            // Full reads walk the whole tree, so they keep the slower cadence.
+            let interval = match extraction_mode {
+                ExtractionMode::Full => full_interval,
+                ExtractionMode::ZoneTargeted => targeted_interval,
+            };
             if (duration < interval) {
                 thread::sleep(interval - duration);
             }
         }
     }
-}
-
 
+    fn extract_ui_tree_address(
+        &mut self,
+        process_id: u32,
+        progress_sink: ProgressSink,
+        cancellation: CancellationToken,
+    ) -> Result<(u64, Vec<UiRootCandidateScore>), String> {
+        let mut database = DatabasePool::global()
+            .and_then(|database_pool| database_pool.get())
+            .map_err(|error| format!("Could not open database: {}", error))?;
+
+        let process = ObtainPidProcess::identify(process_id).ok_or("Could not identify process")?;
+
+        if let Ok(running_processes) = ObtainPidProcess::identify_all("exefile") {
+            match database.delete_stale_root_addresses(&running_processes) {
+                Ok(removed) if removed > 0 => info!("Removed {} stale root addresses", removed),
+                Ok(_) => {}
+                Err(error) => error!("Could not remove stale root addresses: {}", error),
+            }
+        }
 
-fn extract_ui_tree_address(process_id: u32) -> Result<u64, &'static str> {
-    let database = db::database::Database::new("eve.db").unwrap();
+        let cached_root_address = database
+            .get_root_address(&process)
+            .map_err(|error| format!("Could not read cached root address: {}", error))?;
 
+        if let Some(cached_root_address) = cached_root_address {
+            // The address may now hold a login screen or a freed tree, so it has to pass the same checks as a fresh scan.
+            let cached_candidates = UiRootCandidateScorer::score_candidates(process_id, &[cached_root_address]);
+            if cached_candidates.first().is_some_and(|candidate| candidate.has_main_layer) {
+                return Ok((cached_root_address, cached_candidates));
+            }
+            info!("Cached root address {:#X} rejected: {:?}", cached_root_address, cached_candidates.first());
+            if let Err(error) = database.delete_root_address(&process) {
+                self.send_error(process_id, format!("Could not delete cached root address: {}", error));
+            }
+        }
 
+        let (root_address, root_candidates) = get_root_address(&process, &database, progress_sink, cancellation).ok_or("Could not find root address for process")?;
+        if let Err(error) = database.save_root_address(&process, root_address) {
+            self.send_error(process_id, format!("Could not cache root address: {}", error));
+        }
+        Ok((root_address, root_candidates))
+    }
+}
 
-    let database_process_info = database.get_process_info(process_id).unwrap();
+fn get_root_address(
+    process: &ProcessIdentity,
+    database: &Database,
+    progress_sink: ProgressSink,
+    cancellation: CancellationToken,
+) -> Option<(u64, Vec<UiRootCandidateScore>)> {
+    let process_id = process.process_id;
+    let cached_type = database.get_ui_root_type(process).unwrap_or_default();
+
+    let mut extract_possible_root_address = ExtractPossibleRootAddress::with_progress(progress_sink, cancellation);
+    let root_address_scan = extract_possible_root_address
+        .execute_with_cached_type(process_id, cached_type.as_ref())
+        .ok()?;
+    let root_candidates = UiRootCandidateScorer::score_candidates(process_id, &root_address_scan.candidates);
+    for candidate in &root_candidates {
+        info!("Possible root address: {:#X}, score: {:.1}, {:?}", candidate.address, candidate.score, candidate);
+    }
 
-    if (database_process_info.is_none()) {
-        let root_address_optional = get_root_address(process_id);
-        if root_address_optional.is_none() {
-            return Err("Could not find root address for process")
-        }
-        let root_address = root_address_optional.unwrap();
-        database.add_process_info(process_id, root_address.to_string()).unwrap();
-        Ok(root_address)
-    }else{
-        let database_ui_address = database_process_info.unwrap().1.parse::<u64>().unwrap();
-        let ui_extractor = UiTreeNodeExtractor::new(process_id);
-        let ui_tree = ui_extractor.extract_ui_tree_from_address(database_ui_address, 99);
-        if ui_tree.is_err() {
-            let root_address_optional = get_root_address(process_id);
-            if root_address_optional.is_none() {
-                return Err("Could not find root address for process")
-            }
-            database.delete_process_info(process_id).unwrap();
+    let root_address = root_candidates.first()?.address;
 
-            let root_address = root_address_optional.unwrap();
-            database.add_process_info(process_id, root_address.to_string()).unwrap();
-            Ok(root_address)
-        } else {
-            Ok(database_ui_address)
+    let type_object_address = extract_possible_root_address.read_type_object_address(root_address);
+    if let (Some(type_object_address), Some(python_module_base)) = (type_object_address, root_address_scan.python_module_base) {
+        let cached_type = CachedUiRootType { type_object_address, python_module_base };
+        if let Err(error) = database.save_ui_root_type(process, &cached_type) {
+            error!("Could not cache UIRoot type object: {}", error);
         }
     }
+
+    Some((root_address, root_candidates))
 }
 
-fn get_root_address(process_id: u32) -> Option<u64> {
-    let possible_root_address = ExtractPossibleRootAddress::new().execute(process_id).unwrap();
-    let mut ui_tree_nodes: Vec<(Rc<UITreeNodeWithDisplayRegion>,HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>)> = Vec::new();
-    let ui_extractor = UiTreeNodeExtractor::new(process_id);
-    for address in possible_root_address {
-        info!("Possible root address: {:#X}", address);
-        let ui_tree = ui_extractor.extract_ui_tree_from_address(address, 99);
-        if ui_tree.is_err() {
-            continue;
+/// Read durations of one extraction mode between two statistics prints.
+struct ReadDurations {
+    total: Duration,
+    max: Duration,
+    min: Duration,
+    count: u32,
+}
+
+impl ReadDurations {
+    fn new() -> Self {
+        ReadDurations {
+            total: Duration::new(0, 0),
+            max: Duration::new(0, 0),
+            min: Duration::new(u64::MAX, 0),
+            count: 0,
         }
+    }
 
-        ui_tree_nodes.push(ui_tree.unwrap());
+    fn add(&mut self, duration: Duration) {
+        self.total += duration;
+        self.max = self.max.max(duration);
+        self.min = self.min.min(duration);
+        self.count += 1;
     }
-    let ui_address_with_count_nodes = ui_tree_nodes.iter().map(|(ui_tree_node, _)| {
-        let size = ui_tree_node.ui_node.count_descendants();
-        return (size, ui_tree_node);
-    }).collect::<Vec<_>>();
 
-    let largest_ui_address = ui_address_with_count_nodes.iter().max_by(|(size1, _), (size2, _)| size1.cmp(size2));
+    fn average(&self) -> Option<Duration> {
+        (self.count > 0).then(|| self.total / self.count)
+    }
+}
 
-    Some(largest_ui_address?.1.ui_node.object_address)
-}
\ No newline at end of file
+impl fmt::Display for ReadDurations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.average() {
+            Some(average) => write!(f, "Max: {:?}, Min: {:?}, Avg: {:?}, Times: {}", self.max, self.min, average, self.count),
+            None => write!(f, "none"),
+        }
+    }
+}
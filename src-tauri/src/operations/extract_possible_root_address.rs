@@ -1,23 +1,77 @@
 ﻿use crate::eve::interop::memory::utils::MemoryUtils;
 use crate::eve::interop::memory::windows_memory_reader::WindowsMemoryReader;
+use log::info;
 use rayon::prelude::*;
+use serde::Serialize;
 use std::sync::Mutex;
+use std::time::Instant;
+use crate::db::models::CachedUiRootType;
+use crate::operations::scan_progress::{CancellationToken, ProgressSink, ScanPhase, ScanProgress};
+
+const PYTHON_MODULE_NAME: &str = "python27.dll";
 
 pub struct ExtractPossibleRootAddress {
     memory_reader: Option<WindowsMemoryReader>,
+    progress_sink: Option<ProgressSink>,
+    cancellation: CancellationToken,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RootAddressScan {
+    pub candidates: Vec<u64>,
+    pub python_module_base: Option<u64>,
+    pub used_cached_type: bool,
+    pub duration_ms: u64,
 }
 
 impl ExtractPossibleRootAddress {
     pub fn new() -> Box<ExtractPossibleRootAddress> {
         Box::new(ExtractPossibleRootAddress {
             memory_reader: None,
+            progress_sink: None,
+            cancellation: CancellationToken::never(),
+        })
+    }
+
+    pub fn with_progress(progress_sink: ProgressSink, cancellation: CancellationToken) -> Box<ExtractPossibleRootAddress> {
+        Box::new(ExtractPossibleRootAddress {
+            memory_reader: None,
+            progress_sink: Some(progress_sink),
+            cancellation,
         })
     }
 
+    fn start_phase(&self, process_id: u32, phase: ScanPhase, regions_total: usize) -> ScanProgress {
+        ScanProgress::new(process_id, phase, regions_total, self.progress_sink.clone())
+    }
+
+    fn check_cancelled(&self, progress: &ScanProgress) -> Result<(), String> {
+        if self.cancellation.is_cancelled() {
+            progress.finish(ScanPhase::Cancelled);
+            return Err("Root address scan cancelled".to_string());
+        }
+        Ok(())
+    }
+
     pub fn execute(&mut self, process_id: u32) -> Result<Vec<u64>, String> {
+        Ok(self.execute_with_cached_type(process_id, None)?.candidates)
+    }
+
+    /// Looks for UIRoot instances. With a still valid cached type object only the instance scan
+    /// runs; otherwise, or when that finds nothing, the full type and instance scan is done.
+    pub fn execute_with_cached_type(
+        &mut self,
+        process_id: u32,
+        cached_type: Option<&CachedUiRootType>,
+    ) -> Result<RootAddressScan, String> {
+        let start = Instant::now();
+
         self.memory_reader = WindowsMemoryReader::new(process_id);
+        let memory_reader = self.memory_reader.as_ref().ok_or("Could not open process")?;
+
+        let python_module_base = memory_reader.module_base_address(PYTHON_MODULE_NAME);
 
-        let memory_regions = self.memory_reader.as_ref().unwrap().read_commited_region();
+        let memory_regions = memory_reader.read_commited_region();
 
         let mut ordered_memory_regions: Vec<_> = memory_regions
             .into_iter()
@@ -26,36 +80,121 @@ impl ExtractPossibleRootAddress {
 
         ordered_memory_regions.sort_by_key(|region| region.0);
 
+        if let Some(cached_type) = cached_type {
+            if self.is_valid_cached_type(cached_type, python_module_base, memory_reader) {
+                let progress = self.start_phase(process_id, ScanPhase::Instances, ordered_memory_regions.len());
+                let candidates = self.enumerate_candidates_for_instances_of_python_type(
+                    &[cached_type.type_object_address],
+                    &ordered_memory_regions,
+                    process_id,
+                    &progress,
+                );
+                self.check_cancelled(&progress)?;
+
+                if !candidates.is_empty() {
+                    progress.finish(ScanPhase::Finished);
+                    let scan = RootAddressScan {
+                        candidates,
+                        python_module_base,
+                        used_cached_type: true,
+                        duration_ms: start.elapsed().as_millis() as u64,
+                    };
+                    info!("Instance-only root address scan for {} took {} ms", process_id, scan.duration_ms);
+                    return Ok(scan);
+                }
+
+                info!("Cached UIRoot type of {} has no instances, falling back to a full scan", process_id);
+            } else {
+                info!("Cached UIRoot type of {} is no longer valid, falling back to a full scan", process_id);
+            }
+        }
+
         let cloned_memory_regions = ordered_memory_regions.clone();
 
+        let progress = self.start_phase(process_id, ScanPhase::TypeObjects, ordered_memory_regions.len());
         let candidates: Vec<_> = cloned_memory_regions
             .into_par_iter()
             .flat_map(|region| {
-                self.enumerate_candidates_for_python_type_object_type_in_memory_region(
+                if self.cancellation.is_cancelled() {
+                    return Vec::new();
+                }
+                let region_candidates = self.enumerate_candidates_for_python_type_object_type_in_memory_region(
                     region,
                     &WindowsMemoryReader::new(process_id).unwrap(),
-                )
+                );
+                progress.region_scanned(region.1, region_candidates.len());
+                region_candidates
             })
             .collect();
+        self.check_cancelled(&progress)?;
 
+        let progress = self.start_phase(process_id, ScanPhase::UiRootTypes, ordered_memory_regions.len());
         let ui_root_type_object_candidates: Vec<_> = self
             .enumerate_candidates_for_python_type_objects(
                 &ordered_memory_regions,
                 &candidates,
                 &WindowsMemoryReader::new(process_id).unwrap(),
+                &progress,
             )
             .into_iter()
             .filter(|type_object| type_object.1 == "UIRoot")
             .map(|type_object| type_object.0)
             .collect();
+        self.check_cancelled(&progress)?;
 
+        let progress = self.start_phase(process_id, ScanPhase::Instances, ordered_memory_regions.len());
         let candidates = self.enumerate_candidates_for_instances_of_python_type(
             &ui_root_type_object_candidates,
             &ordered_memory_regions,
             process_id,
+            &progress,
         );
+        self.check_cancelled(&progress)?;
+        progress.finish(ScanPhase::Finished);
+
+        let scan = RootAddressScan {
+            candidates,
+            python_module_base,
+            used_cached_type: false,
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
+        info!("Full root address scan for {} took {} ms", process_id, scan.duration_ms);
+
+        Ok(scan)
+    }
+
+    /// Reads the `ob_type` of an instance, which for a UIRoot instance is the UIRoot type object.
+    pub fn read_type_object_address(&self, instance_address: u64) -> Option<u64> {
+        let memory_reader = self.memory_reader.as_ref()?;
+        let memory = memory_reader.read_bytes(instance_address, 0x10).ok()?;
+
+        MemoryUtils::transform_memory_content_as_ulong_memory(&memory).get(1).copied()
+    }
+
+    fn is_valid_cached_type(
+        &self,
+        cached_type: &CachedUiRootType,
+        python_module_base: Option<u64>,
+        windows_memory_reader: &WindowsMemoryReader,
+    ) -> bool {
+        if python_module_base != Some(cached_type.python_module_base) {
+            return false;
+        }
+
+        let type_name = |type_address: u64| {
+            let memory = windows_memory_reader.read_bytes(type_address, 0x20).ok()?;
+            let memory = MemoryUtils::transform_memory_content_as_ulong_memory(&memory);
 
-        Ok(candidates)
+            let name = self.read_null_terminated_ascii_string_from_address_up_to255(memory[3], windows_memory_reader)?;
+            Some((memory[1], name))
+        };
+
+        match type_name(cached_type.type_object_address) {
+            Some((type_of_type, name)) if name == "UIRoot" => {
+                type_name(type_of_type).is_some_and(|(_, name)| name == "type")
+            }
+            _ => false,
+        }
     }
 
     fn enumerate_candidates_for_python_type_object_type_in_memory_region(
@@ -101,6 +240,7 @@ impl ExtractPossibleRootAddress {
         memory_regions: &[(u64, u64)],
         type_object_candidates: &[u64],
         windows_memory_reader: &WindowsMemoryReader,
+        progress: &ScanProgress,
     ) -> Vec<(u64, String)> {
         if type_object_candidates.is_empty() {
             return Vec::new();
@@ -112,14 +252,20 @@ impl ExtractPossibleRootAddress {
         let result = Mutex::new(Vec::new());
 
         memory_regions.into_par_iter().for_each(|&memory_region| {
+            if self.cancellation.is_cancelled() {
+                return;
+            }
+
             let memory_content = self
                 .read_memory_region_content_as_ulong_array(memory_region, windows_memory_reader);
 
             if memory_content.is_none() {
+                progress.region_scanned(memory_region.1, 0);
                 return;
             }
 
             let memory_content = memory_content.unwrap();
+            let mut region_candidates = 0;
 
             for i in 0..memory_content.len() - 4 {
                 let candidate_address = memory_region.0 + (i as u64) * 8;
@@ -140,8 +286,11 @@ impl ExtractPossibleRootAddress {
                     )
                 {
                     result.lock().unwrap().push((candidate_address, candidate_name));
+                    region_candidates += 1;
                 }
             }
+
+            progress.region_scanned(memory_region.1, region_candidates);
         });
 
         /*for &memory_region in memory_regions {
@@ -185,6 +334,7 @@ impl ExtractPossibleRootAddress {
         type_object_candidates: &[u64],
         memory_regions: &[(u64, u64)],
         process_id: u32,
+        progress: &ScanProgress,
     ) -> Vec<u64> {
         if type_object_candidates.is_empty() {
             return Vec::new();
@@ -196,15 +346,21 @@ impl ExtractPossibleRootAddress {
         let result = Mutex::new(Vec::new());
 
         memory_regions.into_par_iter().for_each(|&memory_region| {
+            if self.cancellation.is_cancelled() {
+                return;
+            }
+
             let windows_memory_reader = WindowsMemoryReader::new(process_id).unwrap();
             let memory_content = self
                 .read_memory_region_content_as_ulong_array(memory_region, &windows_memory_reader);
 
             if memory_content.is_none() {
+                progress.region_scanned(memory_region.1, 0);
                 return;
             }
 
             let memory_content = memory_content.unwrap();
+            let mut region_candidates = 0;
 
             for i in 0..memory_content.len() - 4 {
                 let candidate_address = memory_region.0 + (i as u64) * 8;
@@ -216,8 +372,11 @@ impl ExtractPossibleRootAddress {
 
                 if type_object_candidates.contains(&candidate_type) {
                     result.lock().unwrap().push(candidate_address);
+                    region_candidates += 1;
                 }
             }
+
+            progress.region_scanned(memory_region.1, region_candidates);
         });
         /*for &memory_region in memory_regions {
             let memory_content = self
@@ -2,4 +2,9 @@
 pub mod extract_possible_root_address;
 pub mod ui_tree_node_extractor;
 pub mod eve_ui_tracker;
+pub mod sighting_recorder;
+pub mod scan_progress;
+pub mod ui_root_candidate_scorer;
+pub mod tracker_supervisor;
+pub mod character_resolver;
 mod gui_simulation;
\ No newline at end of file
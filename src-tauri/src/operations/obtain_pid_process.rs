@@ -1,4 +1,7 @@
-﻿use sysinfo::System;
+﻿use std::fs;
+use std::path::Path;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use crate::db::models::ProcessIdentity;
 
 pub struct ObtainPidProcess {}
 
@@ -17,4 +20,43 @@ impl ObtainPidProcess {
         
         Ok(processes)
     }
-}
\ No newline at end of file
+
+    pub fn identify(process_id: u32) -> Option<ProcessIdentity> {
+        let mut system = System::new();
+        let pid = Pid::from_u32(process_id);
+
+        system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+
+        let process = system.process(pid)?;
+        let executable_path = process.exe().map(|path| path.to_string_lossy().to_string()).unwrap_or_default();
+
+        Some(ProcessIdentity {
+            process_id,
+            start_time: process.start_time(),
+            client_build: process.exe().and_then(ObtainPidProcess::read_client_build),
+            executable_path,
+        })
+    }
+
+    pub fn identify_all(process_name: &str) -> Result<Vec<ProcessIdentity>, String> {
+        Ok(ObtainPidProcess::execute(process_name)?
+            .into_iter()
+            .filter_map(ObtainPidProcess::identify)
+            .collect())
+    }
+
+    /// The client ships a `start.ini` with its build number next to the `bin64` folder of `exefile.exe`.
+    fn read_client_build(executable_path: &Path) -> Option<String> {
+        let start_ini = executable_path.parent()?.parent()?.join("start.ini");
+        let content = fs::read_to_string(start_ini).ok()?;
+
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            if key.trim().eq_ignore_ascii_case("build") {
+                Some(value.trim().to_string())
+            } else {
+                None
+            }
+        })
+    }
+}
@@ -0,0 +1,116 @@
+﻿use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+pub type ProgressSink = Arc<dyn Fn(&RootAddressScanProgress) + Send + Sync>;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum ScanPhase {
+    TypeObjects,
+    UiRootTypes,
+    Instances,
+    Finished,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RootAddressScanProgress {
+    pub process_id: u32,
+    pub phase: ScanPhase,
+    pub regions_scanned: usize,
+    pub regions_total: usize,
+    pub bytes_scanned: u64,
+    pub candidates_found: usize,
+}
+
+/// Stops a running scan once the tracker's `running` flag is cleared by `stop_tracker`.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    running: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn from_running_flag(running: Arc<AtomicBool>) -> Self {
+        CancellationToken { running }
+    }
+
+    pub fn never() -> Self {
+        CancellationToken {
+            running: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        !self.running.load(Ordering::Relaxed)
+    }
+}
+
+/// Counters for one scan phase, shared by the rayon workers and reported at most every
+/// `REPORT_INTERVAL` so the event sink is not flooded.
+pub struct ScanProgress {
+    process_id: u32,
+    phase: ScanPhase,
+    regions_total: usize,
+    regions_scanned: AtomicUsize,
+    bytes_scanned: AtomicU64,
+    candidates_found: AtomicUsize,
+    last_report: Mutex<Instant>,
+    sink: Option<ProgressSink>,
+}
+
+impl ScanProgress {
+    pub fn new(process_id: u32, phase: ScanPhase, regions_total: usize, sink: Option<ProgressSink>) -> Self {
+        let progress = ScanProgress {
+            process_id,
+            phase,
+            regions_total,
+            regions_scanned: AtomicUsize::new(0),
+            bytes_scanned: AtomicU64::new(0),
+            candidates_found: AtomicUsize::new(0),
+            last_report: Mutex::new(Instant::now()),
+            sink,
+        };
+        progress.report(phase);
+        progress
+    }
+
+    pub fn region_scanned(&self, bytes: u64, candidates: usize) {
+        self.regions_scanned.fetch_add(1, Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(bytes, Ordering::Relaxed);
+        self.candidates_found.fetch_add(candidates, Ordering::Relaxed);
+
+        let should_report = {
+            let mut last_report = self.last_report.lock().unwrap();
+            if last_report.elapsed() >= REPORT_INTERVAL {
+                *last_report = Instant::now();
+                true
+            } else {
+                false
+            }
+        };
+
+        if should_report {
+            self.report(self.phase);
+        }
+    }
+
+    pub fn finish(&self, phase: ScanPhase) {
+        self.report(phase);
+    }
+
+    fn report(&self, phase: ScanPhase) {
+        if let Some(sink) = &self.sink {
+            sink(&RootAddressScanProgress {
+                process_id: self.process_id,
+                phase,
+                regions_scanned: self.regions_scanned.load(Ordering::Relaxed),
+                regions_total: self.regions_total,
+                bytes_scanned: self.bytes_scanned.load(Ordering::Relaxed),
+                candidates_found: self.candidates_found.load(Ordering::Relaxed),
+            });
+        }
+    }
+}
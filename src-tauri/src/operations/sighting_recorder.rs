@@ -0,0 +1,109 @@
+﻿use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use log::error;
+use crate::db::database::DatabaseError;
+use crate::db::pool::DatabasePool;
+use crate::db::sightings::{NewSighting, SightingSource};
+use crate::eve::ui::models::general_window::GeneralWindow;
+
+/// The same pilot in the same ship type, seen by the same source in the same system, is stored at
+/// most once per interval. D-scan sightings have no pilot and are keyed by ship type alone.
+const RECORD_INTERVAL: Duration = Duration::from_secs(300);
+
+type RecordKey = (&'static str, Option<String>, Option<String>, Option<String>);
+
+#[derive(Debug)]
+pub struct SightingRecorder {
+    last_recorded: HashMap<RecordKey, Instant>,
+}
+
+impl SightingRecorder {
+
+    pub fn new() -> Self {
+        SightingRecorder {
+            last_recorded: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, general_window: &GeneralWindow, observer: Option<String>, now: Instant) {
+        self.last_recorded
+            .retain(|_, last_time| now.duration_since(*last_time) < RECORD_INTERVAL);
+
+        let solar_system = general_window.current_solar_system_name();
+        let seen_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default();
+
+        let new_sighting = |pilot_name: Option<String>, ship_type: Option<String>, alliance: Option<String>, source: SightingSource| NewSighting {
+            pilot_name,
+            ship_type,
+            alliance,
+            solar_system: solar_system.clone(),
+            source,
+            observer: observer.clone(),
+            seen_at,
+        };
+
+        let overview_sightings = general_window
+            .overview_windows
+            .iter()
+            .flat_map(|overview| overview.entries.iter())
+            .filter(|entry| entry.is_player)
+            .filter_map(|entry| {
+                Some(new_sighting(
+                    Some(entry.object_name.clone()?),
+                    entry.object_type.clone(),
+                    entry.object_alliance.clone(),
+                    SightingSource::Overview,
+                ))
+            });
+
+        let local_sightings = general_window
+            .local_chat
+            .iter()
+            .flat_map(|local_chat| local_chat.members.iter())
+            .map(|member| new_sighting(Some(member.name.clone()), None, None, SightingSource::Local));
+
+        // D-scan rows name the ship, not the pilot, so only the ship type is recorded.
+        let directional_scanner_sightings = general_window
+            .directional_scanner
+            .iter()
+            .flat_map(|scanner| scanner.entries.iter())
+            .filter(|entry| !entry.ship_type.is_empty() && entry.ship_type != "empty_default")
+            .map(|entry| new_sighting(None, Some(entry.ship_type.clone()), None, SightingSource::DirectionalScanner));
+
+        let sightings = overview_sightings
+            .chain(local_sightings)
+            .chain(directional_scanner_sightings)
+            .filter(|sighting| {
+                let key = (
+                    sighting.source.as_str(),
+                    sighting.pilot_name.clone(),
+                    sighting.ship_type.clone(),
+                    sighting.solar_system.clone(),
+                );
+
+                match self.last_recorded.get(&key) {
+                    Some(last_time) if now.duration_since(*last_time) < RECORD_INTERVAL => false,
+                    _ => {
+                        self.last_recorded.insert(key, now);
+                        true
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if sightings.is_empty() {
+            return;
+        }
+
+        let stored = DatabasePool::global()
+            .and_then(|database_pool| database_pool.get())
+            .and_then(|mut database| database.add_sightings(&sightings).map_err(DatabaseError::from));
+
+        if let Err(error) = stored {
+            error!("Could not store {} sightings: {}", sightings.len(), error);
+        }
+    }
+}
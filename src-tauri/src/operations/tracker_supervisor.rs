@@ -0,0 +1,192 @@
+﻿use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use log::info;
+use serde::Serialize;
+use tauri::{Emitter, Window};
+use crate::alerts::alert_engine::AlertCooldowns;
+use crate::eve::ui::models::general_window::GeneralWindow;
+use crate::operations::eve_ui_tracker::EveUiTracker;
+
+/// Frames from several clients arrive many times per second, the merged state is emitted at most this often.
+const EMIT_INTERVAL: Duration = Duration::from_millis(500);
+
+static SUPERVISOR: OnceLock<Arc<TrackerSupervisor>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientLocation {
+    InSpace,
+    Docked,
+    Unknown,
+}
+
+impl ClientLocation {
+    /// The overview only exists in space, the location panel is shown both docked and in space.
+    pub fn from_general_window(general_window: &GeneralWindow) -> Self {
+        if !general_window.overview_windows.is_empty() {
+            ClientLocation::InSpace
+        } else if general_window.location_info.is_some() {
+            ClientLocation::Docked
+        } else {
+            ClientLocation::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClientState {
+    pub process_id: u32,
+    pub character_name: Option<String>,
+    pub solar_system_name: Option<String>,
+    pub location: ClientLocation,
+    pub hostile_players: Vec<String>,
+    pub updated_at: i64,
+}
+
+impl ClientState {
+    pub fn from_general_window(
+        process_id: u32,
+        character_name: Option<String>,
+        general_window: &GeneralWindow,
+        hostile_players: Vec<String>,
+    ) -> Self {
+        ClientState {
+            process_id,
+            character_name,
+            solar_system_name: general_window.current_solar_system_name(),
+            location: ClientLocation::from_general_window(general_window),
+            hostile_players,
+            updated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MultiClientState {
+    pub clients: Vec<ClientState>,
+}
+
+/// Owns every running tracker and merges their frames into one multi-character state.
+#[derive(Debug)]
+pub struct TrackerSupervisor {
+    window: Arc<Mutex<Window>>,
+    trackers: Mutex<HashMap<u32, Arc<AtomicBool>>>,
+    clients: Mutex<HashMap<u32, ClientState>>,
+    alert_cooldowns: Mutex<HashMap<String, AlertCooldowns>>,
+    last_emitted: Mutex<Option<Instant>>,
+}
+
+impl TrackerSupervisor {
+
+    pub fn init(window: Arc<Mutex<Window>>) {
+        SUPERVISOR
+            .set(Arc::new(TrackerSupervisor {
+                window,
+                trackers: Mutex::new(HashMap::new()),
+                clients: Mutex::new(HashMap::new()),
+                alert_cooldowns: Mutex::new(HashMap::new()),
+                last_emitted: Mutex::new(None),
+            }))
+            .ok();
+    }
+
+    pub fn global() -> Option<Arc<TrackerSupervisor>> {
+        SUPERVISOR.get().cloned()
+    }
+
+    pub fn window(&self) -> Arc<Mutex<Window>> {
+        Arc::clone(&self.window)
+    }
+
+    /// Spawns a tracker for the process, `false` when one is already running.
+    pub fn start_tracker(self: &Arc<Self>, process_id: u32) -> bool {
+        let running = Arc::new(AtomicBool::new(true));
+
+        {
+            let mut trackers = self.trackers.lock().unwrap();
+            if trackers.contains_key(&process_id) {
+                return false;
+            }
+            trackers.insert(process_id, Arc::clone(&running));
+        }
+
+        let mut eve_ui_tracker = EveUiTracker::new(Arc::clone(self), Arc::clone(&running));
+        let supervisor = Arc::clone(self);
+
+        thread::spawn(move || {
+            eve_ui_tracker.start_tracker(process_id);
+            supervisor.tracker_finished(process_id, &running);
+            info!("Stopped tracking process {}", process_id);
+        });
+
+        true
+    }
+
+    pub fn stop_tracker(&self, process_id: u32) -> bool {
+        match self.trackers.lock().unwrap().remove(&process_id) {
+            Some(running) => {
+                running.store(false, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Called by a tracker after every frame.
+    pub fn report(&self, client_state: ClientState) {
+        self.clients.lock().unwrap().insert(client_state.process_id, client_state);
+
+        let emit_due = {
+            let mut last_emitted = self.last_emitted.lock().unwrap();
+            let emit_due = last_emitted.is_none_or(|last_emitted| last_emitted.elapsed() >= EMIT_INTERVAL);
+            if emit_due {
+                *last_emitted = Some(Instant::now());
+            }
+            emit_due
+        };
+
+        if emit_due {
+            self.emit_state();
+        }
+    }
+
+    /// Cooldowns left behind by the character's previous client, if any.
+    pub fn take_alert_cooldowns(&self, character_name: &str) -> AlertCooldowns {
+        self.alert_cooldowns.lock().unwrap().remove(character_name).unwrap_or_default()
+    }
+
+    pub fn store_alert_cooldowns(&self, character_name: &str, cooldowns: AlertCooldowns) {
+        self.alert_cooldowns.lock().unwrap().insert(character_name.to_string(), cooldowns);
+    }
+
+    pub fn state(&self) -> MultiClientState {
+        let mut clients = self.clients.lock().unwrap().values().cloned().collect::<Vec<_>>();
+        clients.sort_by_key(|client| client.process_id);
+
+        MultiClientState { clients }
+    }
+
+    /// Only forgets the process if no newer tracker was started for it in the meantime.
+    fn tracker_finished(&self, process_id: u32, running: &Arc<AtomicBool>) {
+        {
+            let mut trackers = self.trackers.lock().unwrap();
+            match trackers.get(&process_id) {
+                Some(current) if !Arc::ptr_eq(current, running) => return,
+                _ => trackers.remove(&process_id),
+            };
+        }
+        self.clients.lock().unwrap().remove(&process_id);
+        self.emit_state();
+    }
+
+    fn emit_state(&self) {
+        let state = self.state();
+        self.window.lock().unwrap().emit("multi_client_state", state).ok();
+    }
+}
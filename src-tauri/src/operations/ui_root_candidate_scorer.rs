@@ -0,0 +1,220 @@
+﻿use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use serde::Serialize;
+use crate::eve::interop::gui::windows_utils::WindowsUtils;
+use crate::eve::ui::parser_utils::ParserUtils;
+use crate::eve::ui_tree_node::models::ui_tree_node::UITreeNodeWithDisplayRegion;
+use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+use crate::operations::ui_tree_node_extractor::UiTreeNodeExtractor;
+
+const MAIN_LAYER_NAME: &str = "l_main";
+const LAYER_NAME_PREFIX: &str = "l_";
+const WINDOW_SIZE_TOLERANCE: f64 = 0.05;
+const STABILITY_TOLERANCE: f64 = 0.1;
+const STABILITY_READ_DELAY: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UiRootCandidateScore {
+    pub address: u64,
+    pub descendant_count: usize,
+    pub has_main_layer: bool,
+    pub layer_count: usize,
+    pub matches_window_size: bool,
+    pub known_zone_count: usize,
+    pub is_stable: bool,
+    pub score: f64,
+}
+
+/// Ranks UIRoot candidates so a stale or login-screen root does not win just by size.
+pub struct UiRootCandidateScorer {}
+
+impl UiRootCandidateScorer {
+
+    pub fn score_candidates(process_id: u32, candidate_addresses: &[u64]) -> Vec<UiRootCandidateScore> {
+        let ui_extractor = UiTreeNodeExtractor::new(process_id);
+        let client_size = WindowsUtils::get_client_size(process_id);
+
+        let first_reads = candidate_addresses
+            .iter()
+            .filter_map(|&address| {
+                let (ui_tree, zones) = ui_extractor.extract_ui_tree_from_address(address, 99).ok()?;
+                Some((address, ui_tree, zones))
+            })
+            .collect::<Vec<_>>();
+
+        if first_reads.is_empty() {
+            return Vec::new();
+        }
+
+        // The second read for the stability check happens once for all candidates.
+        thread::sleep(STABILITY_READ_DELAY);
+
+        let mut scores = first_reads
+            .iter()
+            .map(|(address, ui_tree, zones)| {
+                let descendant_count = ui_tree.ui_node.count_descendants();
+                let second_descendant_count = ui_extractor
+                    .extract_ui_tree_from_address(*address, 99)
+                    .ok()
+                    .map(|(second_tree, _)| second_tree.ui_node.count_descendants());
+
+                UiRootCandidateScorer::score(*address, ui_tree, zones, descendant_count, second_descendant_count, client_size)
+            })
+            .collect::<Vec<_>>();
+
+        scores.sort_by(|first, second| second.score.total_cmp(&first.score));
+        scores
+    }
+
+    fn score(
+        address: u64,
+        ui_tree: &Arc<UITreeNodeWithDisplayRegion>,
+        zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>,
+        descendant_count: usize,
+        second_descendant_count: Option<usize>,
+        client_size: Option<(i32, i32)>,
+    ) -> UiRootCandidateScore {
+        let layer_names = ui_tree
+            .ui_node
+            .children
+            .iter()
+            .filter_map(|child| ParserUtils::get_name_from_dict_entries(child))
+            .filter(|name| name.starts_with(LAYER_NAME_PREFIX))
+            .collect::<Vec<_>>();
+
+        let has_main_layer = layer_names.iter().any(|name| name == MAIN_LAYER_NAME);
+
+        let root_region = &ui_tree.total_display_region;
+        let matches_window_size = client_size.is_some_and(|(width, height)| {
+            let within_tolerance = |actual: i32, expected: i32| {
+                expected > 0 && ((actual - expected).abs() as f64) <= expected as f64 * WINDOW_SIZE_TOLERANCE
+            };
+            within_tolerance(root_region.width, width) && within_tolerance(root_region.height, height)
+        });
+
+        let known_zone_count = zones.values().filter(|nodes| !nodes.is_empty()).count();
+
+        let is_stable = second_descendant_count.is_some_and(|second_count| {
+            (second_count as f64 - descendant_count as f64).abs() <= descendant_count as f64 * STABILITY_TOLERANCE
+        });
+
+        let mut score = (descendant_count as f64).ln_1p();
+        if has_main_layer {
+            score += 20.0;
+        }
+        score += (layer_names.len().min(5) * 2) as f64;
+        if matches_window_size {
+            score += 15.0;
+        }
+        score += (known_zone_count * 5) as f64;
+        score += if is_stable { 20.0 } else { -50.0 };
+
+        UiRootCandidateScore {
+            address,
+            descendant_count,
+            has_main_layer,
+            layer_count: layer_names.len(),
+            matches_window_size,
+            known_zone_count,
+            is_stable,
+            score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
+    use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
+    use crate::eve::ui_tree_node::ui_constants::UiZonesEnum;
+    use crate::operations::ui_root_candidate_scorer::{UiRootCandidateScore, UiRootCandidateScorer};
+
+    fn layer(name: &str) -> Arc<UiTreeNode> {
+        let mut dict_entries: HashMap<String, Arc<Box<dyn Any + Send + Sync>>> = HashMap::new();
+        dict_entries.insert("_name".to_string(), Arc::new(Box::new(name.to_string())));
+
+        Arc::new(UiTreeNode::new(0, "LayerCore".to_string(), dict_entries, Vec::new(), Vec::new()))
+    }
+
+    fn root(layer_names: &[&str], width: i32, height: i32) -> Arc<UITreeNodeWithDisplayRegion> {
+        let children = layer_names.iter().map(|name| layer(name)).collect();
+        let region = DisplayRegion::new(0, 0, width, height);
+
+        Arc::new(UITreeNodeWithDisplayRegion {
+            ui_node: Arc::new(UiTreeNode::new(0x1000, "UIRoot".to_string(), HashMap::new(), Vec::new(), children)),
+            child_with_region: Vec::new(),
+            child_without_region: Vec::new(),
+            self_display_region: Arc::new(region.clone()),
+            total_display_region: Arc::new(region.clone()),
+            total_display_region_visible: region,
+        })
+    }
+
+    fn score(
+        ui_tree: &Arc<UITreeNodeWithDisplayRegion>,
+        zones: &HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>,
+        second_descendant_count: Option<usize>,
+        client_size: Option<(i32, i32)>,
+    ) -> UiRootCandidateScore {
+        UiRootCandidateScorer::score(0x1000, ui_tree, zones, 1000, second_descendant_count, client_size)
+    }
+
+    #[test]
+    fn counts_only_layers_and_detects_the_main_layer() {
+        let ui_tree = root(&["l_main", "l_menu", "l_hint", "desktop"], 1920, 1080);
+
+        let candidate = score(&ui_tree, &HashMap::new(), Some(1000), None);
+
+        assert!(candidate.has_main_layer);
+        assert_eq!(candidate.layer_count, 3);
+    }
+
+    #[test]
+    fn matches_window_size_within_tolerance() {
+        let ui_tree = root(&["l_main"], 1900, 1060);
+
+        assert!(score(&ui_tree, &HashMap::new(), Some(1000), Some((1920, 1080))).matches_window_size);
+        assert!(!score(&ui_tree, &HashMap::new(), Some(1000), Some((1280, 720))).matches_window_size);
+        assert!(!score(&ui_tree, &HashMap::new(), Some(1000), None).matches_window_size);
+    }
+
+    #[test]
+    fn second_read_decides_stability() {
+        let ui_tree = root(&["l_main"], 1920, 1080);
+
+        assert!(score(&ui_tree, &HashMap::new(), Some(1050), None).is_stable);
+        assert!(!score(&ui_tree, &HashMap::new(), Some(2000), None).is_stable);
+        assert!(!score(&ui_tree, &HashMap::new(), None, None).is_stable);
+    }
+
+    #[test]
+    fn counts_zones_with_nodes() {
+        let ui_tree = root(&["l_main"], 1920, 1080);
+        let zones = HashMap::from([
+            (UiZonesEnum::Overview, vec![UITreeNodeWithDisplayRegion::empty()]),
+            (UiZonesEnum::ProbeScanner, Vec::new()),
+        ]);
+
+        assert_eq!(score(&ui_tree, &zones, Some(1000), None).known_zone_count, 1);
+    }
+
+    #[test]
+    fn in_game_root_outscores_login_screen_and_unstable_roots() {
+        let in_game = root(&["l_main", "l_menu"], 1920, 1080);
+        let login_screen = root(&["l_login", "l_menu"], 1920, 1080);
+        let client_size = Some((1920, 1080));
+
+        let in_game_score = score(&in_game, &HashMap::new(), Some(1000), client_size).score;
+        let login_screen_score = score(&login_screen, &HashMap::new(), Some(1000), client_size).score;
+        let unstable_score = score(&in_game, &HashMap::new(), Some(3000), client_size).score;
+
+        assert!(in_game_score > login_screen_score);
+        assert!(in_game_score > unstable_score);
+        assert!(unstable_score < 0.0);
+    }
+}
@@ -1,58 +1,180 @@
-﻿use crate::eve::interop::memory::memory_reading_cache::MemoryReadingCache;
+﻿use crate::eve::interop::memory::memory_reading_cache::{MemoryReadingCache, MemoryReadingCacheStats};
 use crate::eve::interop::memory::models::dict_entry_representation::PyDictEntryRepresentation;
+use crate::eve::interop::memory::models::memory_read_stats::MemoryReadStats;
 use crate::eve::interop::memory::python_memory_reader::PythonMemoryReader;
 use crate::eve::interop::memory::utils::MemoryUtils;
 use crate::eve::interop::memory::windows_memory_reader::WindowsMemoryReader;
 use crate::eve::interop::memory::python_type_extractor::PythonTypeExtractor;
 use std::any::Any;
-use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use log::debug;
+use rayon::prelude::*;
 use crate::eve::ui_tree_node::models::child_of_node::{ChildWithRegion, ChildWithoutRegion};
 use crate::eve::ui_tree_node::models::display_region::DisplayRegion;
 use crate::eve::ui_tree_node::models::ui_tree_node::{UITreeNodeWithDisplayRegion, UiTreeNode};
 use crate::eve::ui_tree_node::ui_constants::{UiConstants, UiZonesEnum, UI_ZONES};
 use crate::eve::ui_tree_node::utils::display_region_utils::DisplayRegionUtils;
 
+/// Full rereads still happen this often so newly opened windows get picked up.
+const FULL_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Children of this layer are the open windows and panels, read in parallel.
+const PARALLEL_LAYER_NAME: &str = "l_main";
+
+type ZonesWithUi = HashMap<UiZonesEnum, Vec<Arc<UITreeNodeWithDisplayRegion>>>;
+
+/// Where a registered zone subtree was found during the last full pass.
+#[derive(Debug, Clone)]
+pub struct ZoneAnchor {
+    pub zone: UiZonesEnum,
+    pub object_type_name: String,
+    pub address_path: Vec<u64>,
+    pub max_depth: i32,
+    total_display_region: Option<Arc<DisplayRegion>>,
+    occluded_regions: Option<Vec<Arc<DisplayRegion>>>,
+}
+
+impl ZoneAnchor {
+    pub fn address(&self) -> u64 {
+        *self.address_path.last().unwrap()
+    }
+}
+
+/// Collects zone anchors during a full pass. Dropped once inside a zone and on targeted reads.
+#[derive(Clone, Copy)]
+struct AnchorRecorder<'a> {
+    anchors: &'a Mutex<Vec<ZoneAnchor>>,
+    ancestor_path: &'a [u64],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMode {
+    Full,
+    ZoneTargeted,
+}
+
 pub struct UiTreeNodeExtractor {
     windows_memory_reader_ext: PythonMemoryReader,
-    memory_reader: Rc<WindowsMemoryReader>,
+    memory_reader: Arc<WindowsMemoryReader>,
     memory_reading_cache: MemoryReadingCache,
+    zone_anchors: Mutex<Vec<ZoneAnchor>>,
+    last_full_refresh: Mutex<Option<Instant>>,
 }
 
 impl UiTreeNodeExtractor {
     pub fn new(process_id: u32) -> UiTreeNodeExtractor {
-        let memory_reader = Rc::new(WindowsMemoryReader::new(process_id).unwrap());
+        UiTreeNodeExtractor::with_memory_reader(WindowsMemoryReader::new(process_id).unwrap().with_page_cache())
+    }
+
+    /// Reads through the given reader, e.g. one without the page cache to measure what it saves.
+    pub fn with_memory_reader(memory_reader: WindowsMemoryReader) -> UiTreeNodeExtractor {
+        let memory_reader = Arc::new(memory_reader);
 
         UiTreeNodeExtractor {
             windows_memory_reader_ext: PythonMemoryReader::new(&memory_reader),
-            memory_reader: Rc::clone(&memory_reader),
+            memory_reader: Arc::clone(&memory_reader),
             memory_reading_cache: MemoryReadingCache::new(),
+            zone_anchors: Mutex::new(Vec::new()),
+            last_full_refresh: Mutex::new(None),
         }
     }
 
+    pub fn memory_cache_stats(&self) -> MemoryReadingCacheStats {
+        self.memory_reading_cache.stats()
+    }
+
+    /// Memory read counters since the previous call, i.e. of the last frame.
+    pub fn memory_read_stats(&self) -> MemoryReadStats {
+        self.memory_reader.take_read_stats()
+    }
+
+    /// Returns the registered zones, rereading only the zone subtrees remembered from the
+    /// last full pass. Falls back to a full pass when it is due or an anchor went stale.
+    pub fn extract_zones_from_address(
+        &self,
+        address: u64,
+        max_depth: i32,
+    ) -> Result<(ZonesWithUi, ExtractionMode), &'static str> {
+        let full_refresh_due = self
+            .last_full_refresh
+            .lock()
+            .unwrap()
+            .is_none_or(|last_full_refresh| last_full_refresh.elapsed() >= FULL_REFRESH_INTERVAL);
+
+        if !full_refresh_due && !self.zone_anchors.lock().unwrap().is_empty() {
+            match self.extract_zone_anchors() {
+                Ok(zones) => return Ok((zones, ExtractionMode::ZoneTargeted)),
+                Err(error) => debug!("Zone targeted read failed, doing a full read: {}", error),
+            }
+        }
+
+        self.extract_ui_tree_from_address(address, max_depth)
+            .map(|(_, zones)| (zones, ExtractionMode::Full))
+    }
+
+    fn extract_zone_anchors(
+        &self,
+    ) -> Result<ZonesWithUi, &'static str> {
+        self.memory_reading_cache.begin_frame();
+        self.memory_reader.begin_frame();
+        let children_with_zones: Mutex<ZonesWithUi> = Mutex::new(UiConstants::initialize_mapper());
+
+        let zone_anchors = self.zone_anchors.lock().unwrap().clone();
+        for anchor in zone_anchors.iter() {
+            let node = self.read_ui_tree_from_address(
+                anchor.address(),
+                anchor.max_depth,
+                anchor.total_display_region.clone(),
+                anchor.occluded_regions.clone(),
+                &children_with_zones,
+                None,
+            )?;
+
+            if node.ui_node.object_type_name != anchor.object_type_name {
+                return Err("Zone anchor now points to a different object");
+            }
+        }
+
+        Ok(children_with_zones.into_inner().unwrap())
+    }
+
     pub fn extract_ui_tree_from_address(
         &self,
         address: u64,
         max_depth: i32,
-    ) -> Result<(Rc<UITreeNodeWithDisplayRegion>,HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>), &'static str> {
-        self.memory_reading_cache.clear();
-        let children_with_zones: RefCell<
-            HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>,
-        > = RefCell::new(UiConstants::initialize_mapper());
-        
-        let node = self.read_ui_tree_from_address(address, max_depth, None, None, &children_with_zones);
-        node.map(|node| (node,  children_with_zones.into_inner()))
+    ) -> Result<(Arc<UITreeNodeWithDisplayRegion>, ZonesWithUi), &'static str> {
+        self.memory_reading_cache.begin_frame();
+        self.memory_reader.begin_frame();
+        let children_with_zones: Mutex<ZonesWithUi> = Mutex::new(UiConstants::initialize_mapper());
+
+        let zone_anchors = Mutex::new(Vec::new());
+        let anchor_recorder = AnchorRecorder { anchors: &zone_anchors, ancestor_path: &[] };
+
+        let node = self.read_ui_tree_from_address(address, max_depth, None, None, &children_with_zones, Some(anchor_recorder));
+
+        let zone_anchors = if node.is_ok() {
+            *self.last_full_refresh.lock().unwrap() = Some(Instant::now());
+            zone_anchors.into_inner().unwrap()
+        } else {
+            Vec::new()
+        };
+        debug!("Remembered zone anchors: {:?}", zone_anchors);
+        *self.zone_anchors.lock().unwrap() = zone_anchors;
+
+        node.map(|node| (node, children_with_zones.into_inner().unwrap()))
     }
 
     fn read_ui_tree_from_address(
         &self,
         node_address: u64,
         max_depth: i32,
-        total_display_region: Option<Rc<DisplayRegion>>,
-        occluded_regions: Option<Vec<Rc<DisplayRegion>>>,
-        children_with_zones: &RefCell<HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>>,
-    ) -> Result<Rc<UITreeNodeWithDisplayRegion>, &'static str> {
+        total_display_region: Option<Arc<DisplayRegion>>,
+        occluded_regions: Option<Vec<Arc<DisplayRegion>>>,
+        children_with_zones: &Mutex<ZonesWithUi>,
+        anchor_recorder: Option<AnchorRecorder>,
+    ) -> Result<Arc<UITreeNodeWithDisplayRegion>, &'static str> {
         //let mut cache = cache.unwrap_or_else(MemoryReadingCache::new);
         let ui_node_memory = self.memory_reader.read_bytes(node_address, 0x30)?;
 
@@ -67,6 +189,28 @@ impl UiTreeNodeExtractor {
         if python_type_name.is_empty() {
             return Err("Failed to read python type name");
         }
+        // Only the outermost zone node is anchored, nested zones come along with it.
+        let zone = UI_ZONES.get(python_type_name.as_str()).cloned();
+        let mut address_path = Vec::new();
+        if let Some(anchor_recorder) = anchor_recorder {
+            address_path.extend_from_slice(anchor_recorder.ancestor_path);
+            address_path.push(node_address);
+        }
+
+        let zone_anchor = match (&zone, anchor_recorder) {
+            (Some(zone), Some(_)) => {
+                Some(ZoneAnchor {
+                    zone: zone.clone(),
+                    object_type_name: python_type_name.clone(),
+                    address_path: address_path.clone(),
+                    max_depth,
+                    total_display_region: total_display_region.clone(),
+                    occluded_regions: occluded_regions.clone(),
+                })
+            }
+            _ => None,
+        };
+
         //let test = &ui_node_memory[0x10..];
         let dict_address = u64::from_le_bytes(ui_node_memory[0x10..0x18].try_into().unwrap());
         //let dict_address = u64::from_le_bytes(ui_node_memory[0x10..].try_into().unwrap());
@@ -74,7 +218,7 @@ impl UiTreeNodeExtractor {
             .windows_memory_reader_ext
             .read_active_dictionary_entries_from_dictionary_address(dict_address)?;
 
-        let mut dict_entries_of_interest: HashMap<String, Rc<Box<dyn Any>>> = HashMap::new();
+        let mut dict_entries_of_interest: HashMap<String, Arc<Box<dyn Any + Send + Sync>>> = HashMap::new();
         let mut other_dict_entries_keys = Vec::new();
 
         for entry in dictionary_entries.iter() {
@@ -95,9 +239,18 @@ impl UiTreeNodeExtractor {
                 continue;
             }
 
-            let dict_entry_value = self
-                .windows_memory_reader_ext
-                .get_dict_entry_value_representation(entry.value, &self.memory_reading_cache);
+            let long_text_value = if PythonTypeExtractor::is_long_text_key(&key_string) {
+                PythonTypeExtractor::reading_long_text(&self.windows_memory_reader_ext, entry.value, &self.memory_reading_cache)
+                    .ok()
+                    .map(Arc::new)
+            } else {
+                None
+            };
+
+            let dict_entry_value = long_text_value.unwrap_or_else(|| {
+                self.windows_memory_reader_ext
+                    .get_dict_entry_value_representation(entry.value, &self.memory_reading_cache)
+            });
 
             /*if (matches!(&dict_entry_value, _DictEntryValueGenericRepresentation)) {
                 continue;
@@ -140,21 +293,33 @@ impl UiTreeNodeExtractor {
             DisplayRegionUtils::get_display_region_from_dict_entries(&dict_entries_of_interest)
                 .unwrap_or_else(|| DisplayRegion::new(0, 0, 0, 0));
 
-        let cloned_self_display_region = Rc::new(self_display_region);
+        let cloned_self_display_region = Arc::new(self_display_region);
 
         let total_display_region =
-            total_display_region.unwrap_or(Rc::clone(&cloned_self_display_region));
+            total_display_region.unwrap_or(Arc::clone(&cloned_self_display_region));
         let mut occluded_regions = occluded_regions.unwrap_or_else(Vec::new);
 
-        let (children, childs_with_region, childs_without_region, total_display_region_visible) =
+        let children_anchor_recorder = match (&zone, anchor_recorder) {
+            (None, Some(anchor_recorder)) => Some(AnchorRecorder {
+                anchors: anchor_recorder.anchors,
+                ancestor_path: &address_path,
+            }),
+            _ => None,
+        };
+
+        let children_result =
             self.read_childrens(
                 node_address,
                 max_depth,
                 &dict_entries_of_interest,
-                Rc::clone(&total_display_region),
+                Arc::clone(&total_display_region),
                 &mut occluded_regions,
                 children_with_zones,
-            )
+                children_anchor_recorder,
+            );
+
+        let (children, childs_with_region, childs_without_region, total_display_region_visible) =
+            children_result
             .unwrap_or_else(|_| {
                 (
                     Vec::new(),
@@ -173,42 +338,47 @@ impl UiTreeNodeExtractor {
         );
 
         let node_with_display_region = UITreeNodeWithDisplayRegion {
-            ui_node: Rc::new(ui_tree_node),
+            ui_node: Arc::new(ui_tree_node),
             child_with_region: childs_with_region,
             child_without_region: childs_without_region,
-            self_display_region: Rc::clone(&cloned_self_display_region),
-            total_display_region: Rc::clone(&total_display_region),
+            self_display_region: Arc::clone(&cloned_self_display_region),
+            total_display_region: Arc::clone(&total_display_region),
             total_display_region_visible,
         };
 
-        let node = Rc::new(node_with_display_region);
+        let node = Arc::new(node_with_display_region);
         // Add the node to the corresponding zone
         /*if let Some(zone) = UI_ZONES.get(&node.ui_node.object_type_name.as_str()) {
             children_with_zones
                 .borrow_mut()
                 .entry(zone.clone())
                 .or_insert_with(Vec::new)
-                .push(Rc::clone(&node));
+                .push(Arc::clone(&node));
         }*/
 
-        UiConstants::check_and_insert_inportant_zone(children_with_zones, &node.ui_node.object_type_name, Rc::clone(&node));
+        UiConstants::check_and_insert_inportant_zone(children_with_zones, &node.ui_node.object_type_name, Arc::clone(&node));
+
+        if let (Some(zone_anchor), Some(anchor_recorder)) = (zone_anchor, anchor_recorder) {
+            anchor_recorder.anchors.lock().unwrap().push(zone_anchor);
+        }
 
-        Ok(Rc::clone(&node))
+        Ok(Arc::clone(&node))
     }
 
     fn read_childrens(
         &self,
         node_address: u64,
         max_depth: i32,
-        dict_entries_of_interest: &HashMap<String, Rc<Box<dyn Any>>>,
-        total_display_region: Rc<DisplayRegion>,
-        occluded_regions: &mut Vec<Rc<DisplayRegion>>,
-        children_with_zones: &RefCell<HashMap<UiZonesEnum, Vec<Rc<UITreeNodeWithDisplayRegion>>>>,
+        dict_entries_of_interest: &HashMap<String, Arc<Box<dyn Any + Send + Sync>>>,
+        total_display_region: Arc<DisplayRegion>,
+        occluded_regions: &mut Vec<Arc<DisplayRegion>>,
+        children_with_zones: &Mutex<ZonesWithUi>,
+        anchor_recorder: Option<AnchorRecorder>,
     ) -> Result<
         (
-            Vec<Rc<UiTreeNode>>,
-            Vec<Rc<ChildWithRegion>>,
-            Vec<Rc<ChildWithoutRegion>>,
+            Vec<Arc<UiTreeNode>>,
+            Vec<Arc<ChildWithRegion>>,
+            Vec<Arc<ChildWithoutRegion>>,
             DisplayRegion,
         ),
         &'static str,
@@ -218,20 +388,69 @@ impl UiTreeNodeExtractor {
         let child_addresses =
             self.get_children_addresses(node_address, dict_entries_of_interest)?;
 
-        let mut children_tree_nodes: Vec<Rc<UiTreeNode>> = Vec::new();
-        let mut childs_with_region: Vec<Rc<ChildWithRegion>> = Vec::new();
-        let mut childs_without_region: Vec<Rc<ChildWithoutRegion>> = Vec::new();
+        let mut children_tree_nodes: Vec<Arc<UiTreeNode>> = Vec::new();
+        let mut childs_with_region: Vec<Arc<ChildWithRegion>> = Vec::new();
+        let mut childs_without_region: Vec<Arc<ChildWithoutRegion>> = Vec::new();
         let mut occluded_regions_from_siblings = Vec::new();
 
-        for child_address in child_addresses {
-            let child_result = self.read_ui_tree_from_address(
+        // Occluded regions are only handed down, never read, so every child can start
+        // from the same snapshot and siblings do not depend on each other.
+        let occluded_regions_snapshot = occluded_regions.clone();
+        let read_child = |child_address: u64, zones: &Mutex<ZonesWithUi>, anchor_recorder: Option<AnchorRecorder>| {
+            self.read_ui_tree_from_address(
                 child_address,
                 max_depth - 1,
-                Some(Rc::clone(&total_display_region)),
-                Some(occluded_regions.clone()),
-                children_with_zones,
-            );
-            
+                Some(Arc::clone(&total_display_region)),
+                Some(occluded_regions_snapshot.clone()),
+                zones,
+                anchor_recorder,
+            )
+        };
+
+        let read_in_parallel = dict_entries_of_interest
+            .get("_name")
+            .and_then(|name| name.downcast_ref::<String>())
+            .is_some_and(|name| name == PARALLEL_LAYER_NAME);
+
+        let child_results = if read_in_parallel {
+            // Each subtree collects into its own zones and anchors, merged back in child
+            // order so the zone lists come out the same as a sequential read.
+            let subtrees = child_addresses
+                .par_iter()
+                .map(|&child_address| {
+                    let subtree_zones = Mutex::new(HashMap::new());
+                    let subtree_anchors = Mutex::new(Vec::new());
+                    let subtree_anchor_recorder = anchor_recorder.map(|anchor_recorder| AnchorRecorder {
+                        anchors: &subtree_anchors,
+                        ancestor_path: anchor_recorder.ancestor_path,
+                    });
+
+                    let child_result = read_child(child_address, &subtree_zones, subtree_anchor_recorder);
+                    (child_result, subtree_zones.into_inner().unwrap(), subtree_anchors.into_inner().unwrap())
+                })
+                .collect::<Vec<_>>();
+
+            subtrees
+                .into_iter()
+                .map(|(child_result, subtree_zones, subtree_anchors)| {
+                    let mut zones = children_with_zones.lock().unwrap();
+                    for (zone, nodes) in subtree_zones {
+                        zones.entry(zone).or_insert_with(Vec::new).extend(nodes);
+                    }
+                    if let Some(anchor_recorder) = anchor_recorder {
+                        anchor_recorder.anchors.lock().unwrap().extend(subtree_anchors);
+                    }
+                    child_result
+                })
+                .collect::<Vec<_>>()
+        } else {
+            child_addresses
+                .into_iter()
+                .map(|child_address| read_child(child_address, children_with_zones, anchor_recorder))
+                .collect::<Vec<_>>()
+        };
+
+        for child_result in child_results {
             if child_result.is_err() {
                 continue;
             }
@@ -248,10 +467,10 @@ impl UiTreeNodeExtractor {
             );
 
             let child_with_region_option =
-                DisplayRegionUtils::just_case_with_display_region(Rc::clone(&child_result));
+                DisplayRegionUtils::just_case_with_display_region(Arc::clone(&child_result));
 
             if (child_with_region_option.is_some()) {
-                let child_with_region = Rc::clone(&child_with_region_option.unwrap());
+                let child_with_region = Arc::clone(&child_with_region_option.unwrap());
                 let descendants_with_display_region =
                     DisplayRegionUtils::list_descendants_with_display_region(
                         &child_with_region.node.child_with_region,
@@ -265,7 +484,7 @@ impl UiTreeNodeExtractor {
                                 (&child_w_region.node),
                             )
                         })
-                        .map(|child_w_region| Rc::clone(&child_w_region.node.total_display_region)),
+                        .map(|child_w_region| Arc::clone(&child_w_region.node.total_display_region)),
                 );
 
                 childs_with_region.insert(0, child_with_region); // Insert at the start to build the list in reverse order
@@ -281,7 +500,7 @@ impl UiTreeNodeExtractor {
                 ); // Insert at the start to build the list in reverse order
             }
 
-            children_tree_nodes.push(Rc::clone(&child.ui_node));
+            children_tree_nodes.push(Arc::clone(&child.ui_node));
         }
 
         childs_with_region.reverse(); // Reverse to correct the order after processing
@@ -300,7 +519,7 @@ impl UiTreeNodeExtractor {
     fn get_children_addresses(
         &self,
         node_address: u64,
-        dict_entries_of_interest: &HashMap<String, Rc<Box<dyn Any>>>,
+        dict_entries_of_interest: &HashMap<String, Arc<Box<dyn Any + Send + Sync>>>,
     ) -> Result<Vec<u64>, &'static str> {
         let children_dict_entry = dict_entries_of_interest.get("children");
 
@@ -1,6 +1,6 @@
 ﻿import {invoke} from "@tauri-apps/api/core";
 import {listen} from "@tauri-apps/api/event";
-import {UiStatus} from "./UiModel.ts";
+import {RootAddressScanProgress, UiStatus} from "./UiModel.ts";
 import {plainToInstance} from "class-transformer";
 import {useUiEventStore} from "./uiEventStore.ts";
 
@@ -22,6 +22,14 @@ export class UiManager {
       }).then((_) => {
           //TODO: Unlisten
       });
+
+      listen<any>('root_address_scan_progress', (event) => {
+          const progress: RootAddressScanProgress = plainToInstance(RootAddressScanProgress, event.payload);
+
+          this.uiEventStore.onRootAddressScanProgress(progress);
+      }).then((_) => {
+          //TODO: Unlisten
+      });
   }
   
   
@@ -38,6 +38,18 @@ class CellsTexts {
 }
 
 export class OverviewWindowEntry {
+    @Expose({ name: 'entity_id' })
+    entityId!: number | null;
+
+    @Expose({ name: 'object_address' })
+    objectAddress!: number;
+
+    @Expose({ name: 'closing_speed_in_meters_per_second' })
+    closingSpeedInMetersPerSecond!: number | null;
+
+    @Expose({ name: 'time_to_contact_in_seconds' })
+    timeToContactInSeconds!: number | null;
+
     @Expose({ name: 'texts_left_to_right' })
     textsLeftToRight!: string[];
 
@@ -110,6 +122,35 @@ class DirectionalScanner {
     entries!: DirectionalScannerEntry[];
 }
 
+class DisplayRegion {
+    @Expose({ name: 'x' })
+    x!: number;
+
+    @Expose({ name: 'y' })
+    y!: number;
+
+    @Expose({ name: 'width' })
+    width!: number;
+
+    @Expose({ name: 'height' })
+    height!: number;
+}
+
+class UiControl {
+    @Expose({ name: 'name' })
+    name!: string;
+
+    @Expose({ name: 'text' })
+    text!: string | null;
+
+    @Expose({ name: 'is_checked' })
+    isChecked!: boolean | null;
+
+    @Expose({ name: 'display_region' })
+    @Type(() => DisplayRegion)
+    displayRegion!: DisplayRegion;
+}
+
 class ProbeScannerEntry {
     @Expose({ name: 'distance_unformatted' })
     distanceUnformatted!: string;
@@ -126,14 +167,258 @@ class ProbeScannerEntry {
     @Expose({ name: 'signal_strength' })
     signalStrength!: string;
 
+    @Expose({ name: 'signal_strength_percent' })
+    signalStrengthPercent!: number | null;
+
     @Expose({ name: 'type_emplacement' })
     typeEmplacement!: string;
 }
 
+class ProbeScannerProbe {
+    @Expose({ name: 'name' })
+    name!: string;
+
+    @Expose({ name: 'range' })
+    range!: string | null;
+
+    @Expose({ name: 'range_in_au' })
+    rangeInAu!: number | null;
+
+    @Expose({ name: 'expiry_in_seconds' })
+    expiryInSeconds!: number | null;
+}
+
 class ProbeScanner {
     @Expose({ name: 'entries' })
     @Type(() => ProbeScannerEntry)
     entries!: ProbeScannerEntry[];
+
+    @Expose({ name: 'probes' })
+    @Type(() => ProbeScannerProbe)
+    probes!: ProbeScannerProbe[];
+
+    @Expose({ name: 'scan_button' })
+    @Type(() => UiControl)
+    scanButton!: UiControl | null;
+
+    @Expose({ name: 'formation_controls' })
+    @Type(() => UiControl)
+    formationControls!: UiControl[];
+
+    @Expose({ name: 'range_controls' })
+    @Type(() => UiControl)
+    rangeControls!: UiControl[];
+}
+
+class MarketOrderEntry {
+    @Expose({ name: 'cells_texts' })
+    cellsTexts!: Record<string, string>;
+
+    @Expose({ name: 'price' })
+    price!: number | null;
+
+    @Expose({ name: 'quantity' })
+    quantity!: number | null;
+
+    @Expose({ name: 'location' })
+    location!: string | null;
+}
+
+class MarketMyOrderEntry {
+    @Expose({ name: 'cells_texts' })
+    cellsTexts!: Record<string, string>;
+
+    @Expose({ name: 'kind' })
+    kind!: 'Sell' | 'Buy';
+
+    @Expose({ name: 'type_name' })
+    typeName!: string | null;
+
+    @Expose({ name: 'price' })
+    price!: number | null;
+
+    @Expose({ name: 'volume_remaining' })
+    volumeRemaining!: number | null;
+
+    @Expose({ name: 'volume_entered' })
+    volumeEntered!: number | null;
+
+    @Expose({ name: 'station' })
+    station!: string | null;
+
+    @Expose({ name: 'is_outbid' })
+    isOutbid!: boolean;
+
+    @Expose({ name: 'best_competing_price' })
+    bestCompetingPrice!: number | null;
+}
+
+class RegionalMarket {
+    @Expose({ name: 'selected_type_name' })
+    selectedTypeName!: string | null;
+
+    @Expose({ name: 'sell_orders' })
+    @Type(() => MarketOrderEntry)
+    sellOrders!: MarketOrderEntry[];
+
+    @Expose({ name: 'buy_orders' })
+    @Type(() => MarketOrderEntry)
+    buyOrders!: MarketOrderEntry[];
+
+    @Expose({ name: 'my_orders' })
+    @Type(() => MarketMyOrderEntry)
+    myOrders!: MarketMyOrderEntry[];
+}
+
+class HtmlSection {
+    @Expose({ name: 'heading' })
+    heading!: string | null;
+
+    @Expose({ name: 'items' })
+    items!: string[];
+}
+
+class AgentConversationButton {
+    @Expose({ name: 'text' })
+    text!: string;
+
+    @Expose({ name: 'action' })
+    action!: 'Accept' | 'Decline' | 'Complete' | 'Delay' | 'Other';
+}
+
+class AgentConversationWindow {
+    @Expose({ name: 'agent_name' })
+    agentName!: string | null;
+
+    @Expose({ name: 'mission_title' })
+    missionTitle!: string | null;
+
+    @Expose({ name: 'objectives' })
+    objectives!: string[];
+
+    @Expose({ name: 'rewards' })
+    rewards!: string[];
+
+    @Expose({ name: 'bonus_rewards' })
+    bonusRewards!: string[];
+
+    @Expose({ name: 'html_sections' })
+    @Type(() => HtmlSection)
+    htmlSections!: HtmlSection[];
+
+    @Expose({ name: 'buttons' })
+    @Type(() => AgentConversationButton)
+    buttons!: AgentConversationButton[];
+}
+
+class MissionObjective {
+    @Expose({ name: 'text' })
+    text!: string;
+
+    @Expose({ name: 'is_completed' })
+    isCompleted!: boolean;
+}
+
+class TrackedMission {
+    @Expose({ name: 'title' })
+    title!: string | null;
+
+    @Expose({ name: 'objectives' })
+    @Type(() => MissionObjective)
+    objectives!: MissionObjective[];
+
+    @Expose({ name: 'is_completed' })
+    isCompleted!: boolean;
+}
+
+class MissionTrackerPanel {
+    @Expose({ name: 'panel_type' })
+    panelType!: string;
+
+    @Expose({ name: 'missions' })
+    @Type(() => TrackedMission)
+    missions!: TrackedMission[];
+}
+
+class ModuleButtonTooltip {
+    @Expose({ name: 'module_button_region' })
+    @Type(() => DisplayRegion)
+    moduleButtonRegion!: DisplayRegion | null;
+
+    @Expose({ name: 'rows' })
+    rows!: string[];
+
+    @Expose({ name: 'shortcut' })
+    shortcut!: string | null;
+
+    @Expose({ name: 'optimal_range_in_meters' })
+    optimalRangeInMeters!: number | null;
+
+    @Expose({ name: 'falloff_in_meters' })
+    falloffInMeters!: number | null;
+
+    @Expose({ name: 'cycle_time_in_seconds' })
+    cycleTimeInSeconds!: number | null;
+
+    @Expose({ name: 'capacitor_use_in_gj' })
+    capacitorUseInGj!: number | null;
+}
+
+class PlanetExtractor {
+    @Expose({ name: 'rows' })
+    rows!: string[];
+
+    @Expose({ name: 'head_count' })
+    headCount!: number | null;
+
+    @Expose({ name: 'remaining_cycle_time_in_seconds' })
+    remainingCycleTimeInSeconds!: number | null;
+
+    @Expose({ name: 'is_expired' })
+    isExpired!: boolean;
+}
+
+class PlanetStorageContent {
+    @Expose({ name: 'name' })
+    name!: string;
+
+    @Expose({ name: 'quantity' })
+    quantity!: number | null;
+}
+
+class PlanetStorageFacility {
+    @Expose({ name: 'name' })
+    name!: string | null;
+
+    @Expose({ name: 'contents' })
+    @Type(() => PlanetStorageContent)
+    contents!: PlanetStorageContent[];
+
+    @Expose({ name: 'used_capacity_in_m3' })
+    usedCapacityInM3!: number | null;
+
+    @Expose({ name: 'capacity_in_m3' })
+    capacityInM3!: number | null;
+}
+
+class PlanetaryColony {
+    @Expose({ name: 'planet_name' })
+    planetName!: string | null;
+
+    @Expose({ name: 'extractors' })
+    @Type(() => PlanetExtractor)
+    extractors!: PlanetExtractor[];
+
+    @Expose({ name: 'storage_facilities' })
+    @Type(() => PlanetStorageFacility)
+    storageFacilities!: PlanetStorageFacility[];
+
+    @Expose({ name: 'launchpad' })
+    @Type(() => PlanetStorageFacility)
+    launchpad!: PlanetStorageFacility | null;
+
+    @Expose({ name: 'has_expired_extractors' })
+    hasExpiredExtractors!: boolean;
 }
 
 export class RootObject {
@@ -148,6 +433,26 @@ export class RootObject {
     @Expose({ name: 'probe_scanner' })
     @Type(() => ProbeScanner)
     probeScanner!: ProbeScanner;
+
+    @Expose({ name: 'regional_market' })
+    @Type(() => RegionalMarket)
+    regionalMarket!: RegionalMarket | null;
+
+    @Expose({ name: 'agent_conversation_windows' })
+    @Type(() => AgentConversationWindow)
+    agentConversationWindows!: AgentConversationWindow[];
+
+    @Expose({ name: 'mission_tracker_panels' })
+    @Type(() => MissionTrackerPanel)
+    missionTrackerPanels!: MissionTrackerPanel[];
+
+    @Expose({ name: 'module_button_tooltip' })
+    @Type(() => ModuleButtonTooltip)
+    moduleButtonTooltip!: ModuleButtonTooltip | null;
+
+    @Expose({ name: 'planetary_colony' })
+    @Type(() => PlanetaryColony)
+    planetaryColony!: PlanetaryColony | null;
 }
 
 export class UiStatus {
@@ -163,7 +468,30 @@ export class UiStatus {
     
     @Expose({ name: 'process_id'})
     processId!: number
+
+    @Expose({ name: 'character_name'})
+    characterName!: string | null
     
     @Expose({ name: 'error'})
     error!: number
 }
+
+export class RootAddressScanProgress {
+    @Expose({ name: 'process_id' })
+    processId!: number;
+
+    @Expose({ name: 'phase' })
+    phase!: 'TypeObjects' | 'UiRootTypes' | 'Instances' | 'Finished' | 'Cancelled';
+
+    @Expose({ name: 'regions_scanned' })
+    regionsScanned!: number;
+
+    @Expose({ name: 'regions_total' })
+    regionsTotal!: number;
+
+    @Expose({ name: 'bytes_scanned' })
+    bytesScanned!: number;
+
+    @Expose({ name: 'candidates_found' })
+    candidatesFound!: number;
+}
@@ -1,9 +1,10 @@
 ﻿import {defineStore} from "pinia";
 import {ref} from "vue";
-import {OverviewWindowEntry, UiStatus} from "./UiModel.ts";
+import {OverviewWindowEntry, RootAddressScanProgress, UiStatus} from "./UiModel.ts";
 
 export const useUiEventStore = defineStore('uiEvents', () => {
     const actualOverViewEntries = ref<Map<number, OverviewWindowEntry[]>>(new Map<number, OverviewWindowEntry[]>());
+    const rootAddressScanProgress = ref<Map<number, RootAddressScanProgress>>(new Map<number, RootAddressScanProgress>());
     
 
     function onUiUpdated(uiStatus: UiStatus) {
@@ -14,10 +15,16 @@ export const useUiEventStore = defineStore('uiEvents', () => {
 
         actualOverViewEntries.value.set(uiStatus.processId, overViewEntries);
     }
+
+    function onRootAddressScanProgress(progress: RootAddressScanProgress) {
+        rootAddressScanProgress.value.set(progress.processId, progress);
+    }
     
     return {
         overViewEntries: actualOverViewEntries,
-        onUiUpdated
+        rootAddressScanProgress,
+        onUiUpdated,
+        onRootAddressScanProgress
 
     }
 })
\ No newline at end of file
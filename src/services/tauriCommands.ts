@@ -8,4 +8,51 @@ export async function getEveProcess(): Promise<number[]> {
     } catch (error) {
         await Promise.reject(error);
     }
+}
+
+export interface Sighting {
+    pilot_name: string;
+    ship_type: string | null;
+    alliance: string | null;
+    solar_system: string | null;
+    source: string;
+    observer: string | null;
+    seen_at: number;
+}
+
+export interface SightingCount {
+    value: string;
+    sightings: number;
+    last_seen_at: number;
+}
+
+export async function getLastSeen(pilotName: string): Promise<Sighting | null> {
+    return await invoke<Sighting | null>('get_last_seen', {pilotName});
+}
+
+export async function getMostFrequentSystems(pilotName: string, limit?: number): Promise<SightingCount[]> {
+    return await invoke<SightingCount[]>('get_most_frequent_systems', {pilotName, limit});
+}
+
+export async function getShipsFlown(pilotName: string): Promise<SightingCount[]> {
+    return await invoke<SightingCount[]>('get_ships_flown', {pilotName});
+}
+
+export type ClientLocation = 'in_space' | 'docked' | 'unknown';
+
+export interface ClientState {
+    process_id: number;
+    character_name: string | null;
+    solar_system_name: string | null;
+    location: ClientLocation;
+    hostile_players: string[];
+    updated_at: number;
+}
+
+export interface MultiClientState {
+    clients: ClientState[];
+}
+
+export async function getMultiClientState(): Promise<MultiClientState> {
+    return await invoke<MultiClientState>('get_multi_client_state');
 }
\ No newline at end of file